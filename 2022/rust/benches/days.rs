@@ -0,0 +1,40 @@
+use aoc::{read_day_file, with_days, AltRun, Solution};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::hint::black_box;
+
+fn bench_day<S: Solution>(c: &mut Criterion, day: u8) {
+    let Ok(input) = read_day_file(day, "input.txt") else {
+        eprintln!("Skipping day {day:02}: no input.txt");
+        return;
+    };
+    let parsed = S::parse(&input).expect("input should be parsable");
+
+    let mut group = c.benchmark_group(format!("day{day:02}"));
+    group.throughput(Throughput::Bytes(
+        input.len().try_into().expect("input fits in u64"),
+    ));
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+    for alt in S::alternatives() {
+        let name = alt.full_name();
+        match alt.run {
+            AltRun::Parsed(f) => group.bench_function(name, |b| b.iter(|| f(black_box(&parsed)))),
+            AltRun::Raw(f) => group.bench_function(name, |b| b.iter(|| f(black_box(&input)))),
+        };
+    }
+    group.finish();
+}
+
+macro_rules! bench_days {
+    ($($day:literal => $solution:path),* $(,)?) => {
+        fn bench_days(c: &mut Criterion) {
+            $(bench_day::<$solution>(c, $day);)*
+        }
+    };
+}
+
+with_days!(bench_days);
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
@@ -0,0 +1,167 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fmt::Display, fs};
+
+use crate::{resolve_file, Part};
+
+/// Expected answer, numbers and strings are both allowed in the toml.
+/// Inputs that must be rejected use `{ error = "part of the error message" }`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    Error { error: String },
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(n) => n.fmt(f),
+            Self::Text(s) => s.fmt(f),
+            Self::Error { error } => write!(f, "error containing '{error}'"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Expected {
+    pub fn part(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    /// No known answer for this input and part
+    Unknown,
+    Correct,
+    Wrong(Answer),
+}
+
+/// Contents of `data/NN/answers.toml`: expected answers by input file name
+///
+/// ```toml
+/// ["input.txt"]
+/// part1 = 72478
+/// part2 = "FRDSQRRCD"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, Expected>);
+
+impl Answers {
+    pub const FILE_NAME: &'static str = "answers.toml";
+
+    /// Loads the answers for the day, missing file means that there are no known answers
+    pub fn load(day: u8) -> Result<Self> {
+        let path = resolve_file(&format!("{day:02}"), Self::FILE_NAME)?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data =
+            fs::read_to_string(&path).with_context(|| format!("Can't read {}", path.display()))?;
+        Self::parse(&data).with_context(|| format!("Can't parse {}", path.display()))
+    }
+
+    pub fn parse(data: &str) -> Result<Self> {
+        Ok(toml::from_str(data)?)
+    }
+
+    pub fn get(&self, file_name: &str) -> Option<&Expected> {
+        self.0.get(file_name)
+    }
+
+    /// Input file names with their expected answers
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Expected)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn check(&self, file_name: &str, part: Part, answer: &str) -> Check {
+        match self.get(file_name).and_then(|e| e.part(part)) {
+            None => Check::Unknown,
+            Some(expected @ Answer::Error { .. }) => Check::Wrong(expected.clone()),
+            Some(expected) if expected.to_string() == answer => Check::Correct,
+            Some(expected) => Check::Wrong(expected.clone()),
+        }
+    }
+
+    /// Checks a failed part: correct if the answer is an error mentioning the expected text
+    pub fn check_error(&self, file_name: &str, part: Part, error: &anyhow::Error) -> Check {
+        match self.get(file_name).and_then(|e| e.part(part)) {
+            None => Check::Unknown,
+            Some(Answer::Error { error: expected }) if format!("{error:#}").contains(expected) => {
+                Check::Correct
+            }
+            Some(expected) => Check::Wrong(expected.clone()),
+        }
+    }
+
+    /// Checks the result of a part, errors are fine only if they are expected
+    pub fn check_result(&self, file_name: &str, part: Part, answer: &Result<String>) -> Check {
+        match answer {
+            Ok(answer) => self.check(file_name, part, answer),
+            Err(e) => self.check_error(file_name, part, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(
+            r#"
+            ["input.txt"]
+            part1 = 123
+            part2 = "ABC"
+
+            ["test.txt"]
+            part2 = 0
+
+            ["test_empty.txt"]
+            part1 = { error = "Empty" }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(answers.check("input.txt", Part::One, "123"), Check::Correct);
+        assert_eq!(answers.check("input.txt", Part::Two, "ABC"), Check::Correct);
+        assert_eq!(
+            answers.check("input.txt", Part::Two, "ABD"),
+            Check::Wrong(Answer::Text("ABC".to_owned()))
+        );
+        assert_eq!(answers.check("test.txt", Part::One, "1"), Check::Unknown);
+        assert_eq!(answers.check("other.txt", Part::One, "1"), Check::Unknown);
+        assert_eq!(
+            answers.check_error("test_empty.txt", Part::One, &anyhow::anyhow!("Empty input")),
+            Check::Correct
+        );
+        assert!(matches!(
+            answers.check_error("test_empty.txt", Part::One, &anyhow::anyhow!("Other")),
+            Check::Wrong(_)
+        ));
+        assert!(matches!(
+            answers.check("test_empty.txt", Part::One, "0"),
+            Check::Wrong(_)
+        ));
+        assert!(matches!(
+            answers.check_error("input.txt", Part::One, &anyhow::anyhow!("Empty input")),
+            Check::Wrong(_)
+        ));
+        assert_eq!(answers.iter().count(), 3);
+
+        assert!(Answers::parse("[\"input.txt\"]\npart3 = 1").is_err());
+    }
+}
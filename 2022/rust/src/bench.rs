@@ -0,0 +1,347 @@
+use anyhow::{bail, Context, Result};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::HashMap,
+    fmt::{self, Display},
+    fs,
+    hint::black_box,
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{AltRun, Part, Solution};
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// System allocator that keeps track of the allocated memory.
+/// Memory is only reported if it's registered as the `#[global_allocator]`.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn add(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let cur = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(cur, Ordering::Relaxed);
+    }
+
+    fn sub(size: usize) {
+        ALLOCATED.fetch_sub(size, Ordering::Relaxed);
+    }
+
+    fn is_active() -> bool {
+        ALLOCATIONS.load(Ordering::Relaxed) != 0
+    }
+}
+
+// SAFETY: all the actual work is forwarded to the System allocator
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::sub(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::sub(layout.size());
+            Self::add(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub samples: usize,
+    pub warmup_time: Duration,
+    pub measurement_time: Duration,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            samples: 50,
+            warmup_time: Duration::from_millis(300),
+            measurement_time: Duration::from_secs(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Memory {
+    /// Max memory allocated on top of what was allocated before the run
+    pub peak: usize,
+    pub allocations: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    /// `parse`, `part1`, `part2` or `part1/<alternative name>`
+    pub name: String,
+    /// Nanoseconds per iteration
+    pub mean: f64,
+    pub stddev: f64,
+    /// Size of the input, for throughput
+    pub bytes: usize,
+    pub memory: Option<Memory>,
+}
+
+impl Measurement {
+    /// Bytes per second
+    pub fn throughput(&self) -> f64 {
+        #![allow(clippy::as_conversions, clippy::cast_precision_loss)]
+        self.bytes as f64 / self.mean * 1e9
+    }
+}
+
+fn measure_memory<T>(f: &mut impl FnMut() -> T) -> Option<Memory> {
+    let start = ALLOCATED.load(Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    PEAK.store(start, Ordering::Relaxed);
+    black_box(f());
+    CountingAlloc::is_active().then(|| Memory {
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(start),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+    })
+}
+
+pub fn measure<T>(
+    name: impl Into<String>,
+    bytes: usize,
+    opts: &Options,
+    mut f: impl FnMut() -> T,
+) -> Measurement {
+    #![allow(
+        clippy::as_conversions,
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+
+    let memory = measure_memory(&mut f);
+
+    // Warmup, also estimates how long a single iteration takes
+    let start = Instant::now();
+    let mut iters = 0_u64;
+    while start.elapsed() < opts.warmup_time || iters == 0 {
+        black_box(f());
+        iters += 1;
+    }
+    let per_iter = start.elapsed().as_secs_f64() / iters as f64;
+    let samples = opts.samples.max(2);
+    let sample_time = opts.measurement_time.as_secs_f64() / samples as f64;
+    let iters_per_sample = ((sample_time / per_iter) as u64).max(1);
+
+    let times: Vec<f64> = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..iters_per_sample {
+                black_box(f());
+            }
+            start.elapsed().as_nanos() as f64 / iters_per_sample as f64
+        })
+        .collect();
+    let mean = times.iter().sum::<f64>() / times.len() as f64;
+    let variance = times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (times.len() - 1) as f64;
+
+    Measurement {
+        name: name.into(),
+        mean,
+        stddev: variance.sqrt(),
+        bytes,
+        memory,
+    }
+}
+
+/// Measures parsing, both parts and all the alternatives separately.
+/// Alternatives must produce the same answers as the main implementation.
+pub fn bench<S: Solution>(input: &str, opts: &Options) -> Result<Vec<Measurement>> {
+    let bytes = input.len();
+    let parsed = S::parse(input).context("Failed to parse input")?;
+    let answer1 = S::part1(&parsed)?.to_string();
+    let answer2 = S::part2(&parsed)?.to_string();
+
+    let mut res = vec![
+        measure("parse", bytes, opts, || S::parse(input)),
+        measure("part1", bytes, opts, || S::part1(&parsed)),
+        measure("part2", bytes, opts, || S::part2(&parsed)),
+    ];
+    for alt in S::alternatives() {
+        let name = alt.full_name();
+        let expected = match alt.part {
+            Part::One => &answer1,
+            Part::Two => &answer2,
+        };
+        let answer = match alt.run {
+            AltRun::Parsed(f) => f(&parsed),
+            AltRun::Raw(f) => f(input),
+        }
+        .with_context(|| format!("{name} failed"))?;
+        if &answer != expected {
+            bail!("{name} returned {answer}, expected {expected}");
+        }
+        res.push(match alt.run {
+            AltRun::Parsed(f) => measure(name, bytes, opts, || f(&parsed)),
+            AltRun::Raw(f) => measure(name, bytes, opts, || f(input)),
+        });
+    }
+    Ok(res)
+}
+
+/// Mean and stddev of previous runs by day and measurement name
+#[derive(Debug, Default)]
+pub struct Baseline(HashMap<(u8, String), (f64, f64)>);
+
+impl Baseline {
+    /// Reads the tab separated `day name mean stddev` file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let data = fs::read_to_string(path)
+            .with_context(|| format!("Can't read baseline {}", path.display()))?;
+        data.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let mut fields = line.split('\t');
+                let mut next = || fields.next().with_context(|| format!("Bad line {line}"));
+                let day = next()?.parse()?;
+                let name = next()?.to_owned();
+                let mean = next()?.parse()?;
+                let stddev = next()?.parse()?;
+                Ok(((day, name), (mean, stddev)))
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut entries: Vec<_> = self.0.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        let data: String = entries
+            .into_iter()
+            .map(|((day, name), (mean, stddev))| format!("{day}\t{name}\t{mean}\t{stddev}\n"))
+            .collect();
+        let path = path.as_ref();
+        fs::write(path, data).with_context(|| format!("Can't write baseline {}", path.display()))
+    }
+
+    pub fn insert(&mut self, day: u8, m: &Measurement) {
+        self.0.insert((day, m.name.clone()), (m.mean, m.stddev));
+    }
+
+    /// Relative change of the mean compared to the baseline and whether it's bigger than the noise
+    /// (`mean ± stddev` intervals don't overlap). `None` if the baseline mean isn't positive,
+    /// there's no relative change from zero.
+    pub fn compare(&self, day: u8, m: &Measurement) -> Option<(f64, bool)> {
+        let &(mean, stddev) = self.0.get(&(day, m.name.clone()))?;
+        (mean > 0.0).then(|| {
+            let significant = (m.mean - mean).abs() > m.stddev + stddev;
+            (m.mean / mean - 1.0, significant)
+        })
+    }
+}
+
+/// Formats nanoseconds
+pub struct Time(pub f64);
+
+impl Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (val, unit) = match self.0 {
+            t if t < 1e3 => (t, "ns"),
+            t if t < 1e6 => (t / 1e3, "µs"),
+            t if t < 1e9 => (t / 1e6, "ms"),
+            t => (t / 1e9, "s"),
+        };
+        write!(f, "{val:.2} {unit}")
+    }
+}
+
+/// Formats number of bytes
+pub struct Bytes(pub f64);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (val, unit) = match self.0 {
+            b if b < 1024.0 => return write!(f, "{b:.0} B"),
+            b if b < 1024.0_f64.powi(2) => (b / 1024.0, "KiB"),
+            b if b < 1024.0_f64.powi(3) => (b / 1024.0_f64.powi(2), "MiB"),
+            b => (b / 1024.0_f64.powi(3), "GiB"),
+        };
+        write!(f, "{val:.2} {unit}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(Time(12.0).to_string(), "12.00 ns");
+        assert_eq!(Time(1_500_000.0).to_string(), "1.50 ms");
+        assert_eq!(Bytes(10.0).to_string(), "10 B");
+        assert_eq!(Bytes(512.000_000_1).to_string(), "512 B");
+        assert_eq!(Bytes(3.0 * 1024.0 * 1024.0).to_string(), "3.00 MiB");
+    }
+
+    #[test]
+    fn test_baseline() {
+        let m = Measurement {
+            name: "part1".to_owned(),
+            mean: 150.0,
+            stddev: 1.0,
+            bytes: 0,
+            memory: None,
+        };
+        let mut baseline = Baseline::default();
+        assert_eq!(baseline.compare(1, &m), None);
+        baseline.insert(
+            1,
+            &Measurement {
+                mean: 100.0,
+                ..m.clone()
+            },
+        );
+
+        let path = std::env::temp_dir().join(format!("aoc-baseline-{}.tsv", std::process::id()));
+        baseline.save(&path).unwrap();
+        let baseline = Baseline::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(baseline.compare(1, &m), Some((0.5, true)));
+        assert_eq!(baseline.compare(2, &m), None);
+        let noisy = Measurement {
+            stddev: 60.0,
+            ..m.clone()
+        };
+        assert_eq!(baseline.compare(1, &noisy), Some((0.5, false)));
+
+        let mut zero = Baseline::default();
+        zero.insert(
+            1,
+            &Measurement {
+                mean: 0.0,
+                ..m.clone()
+            },
+        );
+        assert_eq!(zero.compare(1, &m), None);
+    }
+}
@@ -0,0 +1,468 @@
+use anyhow::{anyhow, bail, Context, Result};
+use aoc::{
+    answers::{Answers, Check},
+    bench::{self, Baseline, Bytes, CountingAlloc, Time},
+    days::{
+        self,
+        day07::{Day07, Params},
+        Day,
+    },
+    diagnostic::Diagnostic,
+    remote::{self, Client, Fetched, UreqBackend, Verdict},
+    InputSource, Part, Solution,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::{ops::RangeInclusive, path::PathBuf, process::ExitCode, str::FromStr, time::Duration};
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code 2022 solutions")]
+struct Cli {
+    /// Dir with the `NN/input.txt` files, can also be set with `AOC_DATA_DIR`.
+    /// Defaults to `data` of the checkout, which is not used when a dir is given.
+    #[arg(long, global = true)]
+    data_dir: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run solutions for the selected days, answers are checked against `answers.toml`
+    Run {
+        /// Days to run: `05`, `1-4`, `1,3,6-8` or `all`
+        #[arg(default_value = "all")]
+        days: Days,
+        /// Run only this part
+        #[arg(short, long, value_parser = parse_part)]
+        part: Option<Part>,
+        /// File name inside the day's data dir, path (with a `/`) or `-` for stdin.
+        /// Stdin is only read when asked for, piped input is ignored otherwise.
+        #[arg(short, long, default_value = "input.txt")]
+        input: InputSource,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = Format::Human)]
+        format: Format,
+    },
+    /// Measure time and memory of parsing, parts and alternative implementations
+    Bench(BenchArgs),
+    /// Download inputs into `NN/input.txt` of the data dir, already downloaded ones are skipped
+    Fetch {
+        /// Days to download: `05`, `1-4`, `1,3,6-8`
+        days: Days,
+        #[command(flatten)]
+        remote: RemoteArgs,
+    },
+    /// Run a part and submit its answer, every guess is kept in `NN/guesses.tsv` of the data dir.
+    /// Answers that are known to be wrong from the earlier guesses aren't submitted.
+    Submit {
+        day: u8,
+        #[arg(value_parser = parse_part)]
+        part: Part,
+        /// File name inside the day's data dir, path (with a `/`) or `-` for stdin
+        #[arg(short, long, default_value = "input.txt")]
+        input: InputSource,
+        #[command(flatten)]
+        remote: RemoteArgs,
+    },
+    /// Show the filesystem reconstructed from the day 07 terminal output, or query it
+    /// with other sizes than in the puzzle
+    Day07 {
+        #[arg(value_enum)]
+        view: FsView,
+        /// File name inside the day's data dir, path (with a `/`) or `-` for stdin
+        #[arg(short, long, default_value = "input.txt")]
+        input: InputSource,
+        /// Levels of dirs to show in the `tree` view
+        #[arg(short = 'L', long)]
+        depth: Option<usize>,
+        #[command(flatten)]
+        params: FsParams,
+    },
+}
+
+#[derive(Debug, Args)]
+struct FsParams {
+    /// Size of the disk for `delete`
+    #[arg(long, default_value_t = Params::default().disk_size)]
+    disk_size: usize,
+    /// Free space that `delete` should get
+    #[arg(long, default_value_t = Params::default().needed_space)]
+    needed_space: usize,
+    /// Largest dir that `small` counts
+    #[arg(long, default_value_t = Params::default().small_dir_limit)]
+    small_dir_limit: usize,
+}
+
+impl From<&FsParams> for Params {
+    fn from(args: &FsParams) -> Self {
+        Self {
+            disk_size: args.disk_size,
+            needed_space: args.needed_space,
+            small_dir_limit: args.small_dir_limit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum FsView {
+    /// Every dir and file with its size, like `tree --du`
+    Tree,
+    /// Total size of every dir, like `du -h`
+    Du,
+    /// Flat list of the dirs with their parent, files and sizes
+    Json,
+    /// Sum of the sizes of the small dirs (part 1)
+    Small,
+    /// Size of the smallest dir to delete to get the needed free space (part 2)
+    Delete,
+}
+
+#[derive(Debug, Args)]
+struct RemoteArgs {
+    /// Session cookie of the logged in user, can also be set with `AOC_SESSION`
+    #[arg(long)]
+    session: Option<String>,
+    /// Minimal time between requests to the server, in seconds
+    #[arg(long, default_value_t = 5)]
+    interval: u64,
+    #[arg(long, default_value = remote::BASE_URL, hide = true)]
+    base_url: String,
+}
+
+impl RemoteArgs {
+    fn client(&self) -> Result<Client<UreqBackend>> {
+        let session = match &self.session {
+            Some(session) => session.clone(),
+            None => std::env::var(remote::SESSION_ENV)
+                .with_context(|| format!("Pass --session or set {}", remote::SESSION_ENV))?,
+        };
+        Ok(Client::new(
+            UreqBackend::default(),
+            &self.base_url,
+            session,
+            aoc::data_dir()?,
+            Duration::from_secs(self.interval),
+        ))
+    }
+}
+
+#[derive(Debug, Args)]
+struct BenchArgs {
+    /// Days to benchmark: `05`, `1-4`, `1,3,6-8` or `all`
+    #[arg(default_value = "all")]
+    days: Days,
+    /// File name inside the day's data dir, path (with a `/`) or `-` for stdin
+    #[arg(short, long, default_value = "input.txt")]
+    input: InputSource,
+    /// Number of samples per measurement
+    #[arg(long, default_value_t = 50)]
+    samples: usize,
+    /// Time spent collecting samples for each measurement, in ms
+    #[arg(long, default_value_t = 1000)]
+    time: u64,
+    /// Warmup time before each measurement, in ms
+    #[arg(long, default_value_t = 300)]
+    warmup: u64,
+    /// Compare with the baseline saved with `--save-baseline`
+    #[arg(short, long)]
+    baseline: Option<PathBuf>,
+    /// Save the results as a new baseline
+    #[arg(long)]
+    save_baseline: Option<PathBuf>,
+    /// Slowdown compared to the baseline (in %) that is reported as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// `Day 05 part 1: CMZ`
+    Human,
+    /// Tab-separated `day part answer correct|wrong|unknown`, errors go to stderr
+    Tsv,
+}
+
+#[derive(Debug, Clone)]
+struct Days(Vec<RangeInclusive<u8>>);
+
+impl Days {
+    fn contains(&self, day: u8) -> bool {
+        self.0.iter().any(|r| r.contains(&day))
+    }
+}
+
+impl FromStr for Days {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Self(vec![u8::MIN..=u8::MAX]));
+        }
+        s.split(',')
+            .map(|part| {
+                let parse = |s: &str| {
+                    s.trim()
+                        .parse::<u8>()
+                        .with_context(|| format!("Incorrect day '{s}'"))
+                };
+                Ok(match part.split_once('-') {
+                    Some((from, to)) => parse(from)?..=parse(to)?,
+                    None => {
+                        let day = parse(part)?;
+                        day..=day
+                    }
+                })
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+}
+
+fn parse_part(s: &str) -> Result<Part> {
+    Part::try_from(s.parse::<u8>().context("Part should be a number")?)
+}
+
+fn select(days: &Days) -> Result<Vec<&'static Day>> {
+    let selected: Vec<_> = days::DAYS.iter().filter(|d| days.contains(d.day)).collect();
+    if selected.is_empty() {
+        bail!("No solutions for the selected days");
+    }
+    Ok(selected)
+}
+
+/// Stdin and arbitrary paths only make sense for a single day
+fn check_input(days: &[&Day], input: &InputSource) -> Result<()> {
+    if input.data_file().is_none() && days.len() > 1 {
+        bail!("Input {input} can only be used with a single day");
+    }
+    Ok(())
+}
+
+/// Name of the input in the diagnostics
+fn file_name(day: u8, input: &InputSource) -> String {
+    match input {
+        InputSource::DataFile(name) => format!("{day:02}/{name}"),
+        input => input.to_string(),
+    }
+}
+
+fn run(days: &Days, part: Option<Part>, input: &InputSource, format: Format) -> Result<()> {
+    let parts = part.as_ref().map_or(&Part::BOTH[..], std::slice::from_ref);
+    let days = select(days)?;
+    check_input(&days, input)?;
+    // Answers are only known for the files in the data dir
+    let input_name = input.data_file().unwrap_or_default();
+
+    let mut failed = 0;
+    for day in days {
+        let input_data = match input.read(day.day) {
+            Ok(input_data) => input_data,
+            Err(e) => {
+                failed += parts.len();
+                eprintln!("Day {:02} failed: {e:?}", day.day);
+                continue;
+            }
+        };
+        let answers = (day.solve)(&input_data, parts).unwrap_or_else(|mut e| {
+            Diagnostic::set_file(&mut e, file_name(day.day, input));
+            // Failed to parse, so all the parts fail with the same error
+            parts
+                .iter()
+                .map(|&part| (part, Err(anyhow!("{e:#}"))))
+                .collect()
+        });
+        let expected = Answers::load(day.day).unwrap_or_else(|e| {
+            eprintln!("Day {:02}: can't load answers: {e:?}", day.day);
+            Answers::default()
+        });
+        for (part, answer) in answers {
+            let answer = match answer {
+                Ok(answer) => answer,
+                Err(e) => {
+                    if expected.check_error(input_name, part, &e) == Check::Correct {
+                        eprintln!("Day {:02} part {part} failed as expected: {e:#}", day.day);
+                    } else {
+                        failed += 1;
+                        eprintln!("Day {:02} part {part} failed: {e:?}", day.day);
+                    }
+                    continue;
+                }
+            };
+            let check = expected.check(input_name, part, &answer);
+            if matches!(check, Check::Wrong(_)) {
+                failed += 1;
+            }
+            match format {
+                Format::Human => {
+                    let check = match check {
+                        Check::Unknown => String::new(),
+                        Check::Correct => " (correct)".to_owned(),
+                        Check::Wrong(expected) => format!(" (WRONG, expected {expected})"),
+                    };
+                    println!("Day {:02} part {part}: {answer}{check}", day.day);
+                }
+                Format::Tsv => {
+                    let check = match check {
+                        Check::Unknown => "unknown",
+                        Check::Correct => "correct",
+                        Check::Wrong(_) => "wrong",
+                    };
+                    println!("{:02}\t{part}\t{answer}\t{check}", day.day);
+                }
+            }
+        }
+    }
+    if failed != 0 {
+        bail!("{failed} part(s) failed");
+    }
+    Ok(())
+}
+
+fn run_bench(args: &BenchArgs) -> Result<()> {
+    let opts = bench::Options {
+        samples: args.samples,
+        warmup_time: Duration::from_millis(args.warmup),
+        measurement_time: Duration::from_millis(args.time),
+    };
+    let baseline = args.baseline.as_ref().map(Baseline::load).transpose()?;
+    let mut new_baseline = Baseline::default();
+    let mut regressions = 0;
+
+    let days = select(&args.days)?;
+    check_input(&days, &args.input)?;
+    for day in days {
+        let input = args.input.read(day.day)?;
+        let measurements =
+            (day.bench)(&input, &opts).with_context(|| format!("Day {:02} failed", day.day))?;
+        println!("Day {:02} ({})", day.day, Bytes(usize_to_f64(input.len())));
+        for m in &measurements {
+            print!(
+                "  {:<24}{:>12} ± {:<12}{:>12}/s",
+                m.name,
+                Time(m.mean).to_string(),
+                Time(m.stddev).to_string(),
+                Bytes(m.throughput()).to_string(),
+            );
+            if let Some(mem) = m.memory {
+                print!(
+                    "{:>12} peak{:>8} allocs",
+                    Bytes(usize_to_f64(mem.peak)).to_string(),
+                    mem.allocations
+                );
+            }
+            if let Some((change, significant)) =
+                baseline.as_ref().and_then(|b| b.compare(day.day, m))
+            {
+                let change = change * 100.0;
+                print!("  {change:+.1}%");
+                if significant && change > args.threshold {
+                    regressions += 1;
+                    print!(" REGRESSION");
+                }
+            }
+            println!();
+            new_baseline.insert(day.day, m);
+        }
+    }
+
+    if let Some(path) = &args.save_baseline {
+        new_baseline.save(path)?;
+    }
+    if regressions != 0 {
+        bail!("{regressions} regression(s) compared to the baseline");
+    }
+    Ok(())
+}
+
+fn fetch(days: &Days, remote: &RemoteArgs) -> Result<()> {
+    let client = remote.client()?;
+    for day in 1..=25 {
+        if !days.contains(day) {
+            continue;
+        }
+        match client.fetch_input(day)? {
+            Fetched::Cached(path) => println!("Day {day:02}: already at {}", path.display()),
+            Fetched::Downloaded(path) => println!("Day {day:02}: saved to {}", path.display()),
+        }
+    }
+    Ok(())
+}
+
+fn submit(day: u8, part: Part, input: &InputSource, remote: &RemoteArgs) -> Result<()> {
+    let solution = days::get(day).with_context(|| format!("No solution for day {day}"))?;
+    let input = input.read(day)?;
+    let (_, answer) = (solution.solve)(&input, &[part])?
+        .pop()
+        .context("No answer")?;
+    let answer = answer.with_context(|| format!("Day {day:02} part {part} failed"))?;
+
+    let verdict = remote.client()?.submit(day, part, &answer)?;
+    println!("Day {day:02} part {part}: {answer} is {verdict}");
+    if verdict != Verdict::Correct {
+        bail!("Wrong answer");
+    }
+    Ok(())
+}
+
+fn day07(view: FsView, input: &InputSource, depth: Option<usize>, params: &Params) -> Result<()> {
+    let text = input.read(7)?;
+    let tree = Day07::parse(&text).map_err(|mut e| {
+        Diagnostic::set_file(&mut e, file_name(7, input));
+        e
+    })?;
+    match view {
+        FsView::Tree => print!("{}", tree.render_tree(depth)),
+        FsView::Du => print!("{}", tree.du()),
+        FsView::Json => println!("{}", serde_json::to_string_pretty(&tree)?),
+        FsView::Small => println!("{}", tree.sum_small_dirs(params)),
+        FsView::Delete => println!("{}", tree.dir_to_delete(params)?),
+    }
+    Ok(())
+}
+
+fn usize_to_f64(v: usize) -> f64 {
+    #![allow(clippy::as_conversions, clippy::cast_precision_loss)]
+    v as f64
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Some(dir) = cli.data_dir {
+        if let Err(e) = aoc::set_data_dir(dir) {
+            eprintln!("Error: {e:?}");
+            return ExitCode::FAILURE;
+        }
+    }
+    let res = match cli.command {
+        Command::Run {
+            days,
+            part,
+            input,
+            format,
+        } => run(&days, part, &input, format),
+        Command::Bench(args) => run_bench(&args),
+        Command::Fetch { days, remote } => fetch(&days, &remote),
+        Command::Submit {
+            day,
+            part,
+            input,
+            remote,
+        } => submit(day, part, &input, &remote),
+        Command::Day07 {
+            view,
+            input,
+            depth,
+            params,
+        } => day07(view, &input, depth, &(&params).into()),
+    };
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    extra_itertools::ExtraItertools,
+    lines::{at_line, Lines},
+    AltRun, Alternative, Part, Solution,
+};
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use std::io::prelude::*;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    // calories carried by each elf
+    type Input<'input> = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Lines::new(input)
+            .groups()
+            .map(|elf| {
+                elf.into_iter()
+                    .map(|rec| at_line(rec, rec.1.parse::<u64>().context("Failed to parse")))
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<u64> {
+        let mut max = None;
+        for &cur_sum in input {
+            if !matches!(max, Some(sum) if cur_sum <= sum) {
+                max = Some(cur_sum);
+            }
+        }
+
+        max.context("No elves in data")
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<u64> {
+        let top = input
+            .iter()
+            .copied()
+            .top_k::<3>()
+            .context("Less than 3 elves in data")?;
+        Ok(top.iter().sum())
+    }
+
+    fn alternatives() -> Vec<Alternative<Self>> {
+        vec![Alternative {
+            name: "full_iter",
+            part: Part::One,
+            run: AltRun::Raw(|input| part1_full_iter(input.as_bytes()).map(|r| r.to_string())),
+        }]
+    }
+}
+
+// I WANTED to go full iterator on this problem. I succeeded (and it's not even that bad now)
+pub fn part1_full_iter(r: impl BufRead) -> Result<u64> {
+    r.lines()
+        .map(|line| line.context("Failed to read the line"))
+        .groups_by_blank_lines()
+        .map_ok_res(|elf| {
+            elf.iter()
+                .map(|line| line.trim().parse::<u64>().context("Failed to parse"))
+                .sum::<Result<u64>>()
+        })
+        .enumerate_errors()
+        // None < Some, so the result is None only if there are no elves
+        .fold_ok(None, |max, sum| max.max(Some(sum)))?
+        .context("No elves in data")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read;
+
+    #[test]
+    fn test_part1() {
+        let input = read!("test.txt").unwrap();
+        assert_eq!(Day01::solve_part1(&input).unwrap(), 24000);
+        assert_eq!(part1_full_iter(input.as_bytes()).unwrap(), 24000);
+        let err = part1_full_iter("1\n\n2\nx\n".as_bytes()).unwrap_err();
+        assert!(format!("{err:#}").starts_with("Element 2: Failed to parse"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Day01::solve_part2(&read!("test.txt").unwrap()).unwrap(),
+            45000
+        );
+    }
+}
@@ -1,10 +1,6 @@
+use crate::{lines::Lines, Solution};
 use anyhow::{bail, Context, Result};
-use aoc::open;
-use std::{
-    fs::File,
-    io::{prelude::*, BufReader},
-    str::FromStr,
-};
+use std::str::FromStr;
 
 trait Score {
     fn score(self) -> u64;
@@ -97,69 +93,78 @@ impl FromStr for Outcome {
     }
 }
 
+fn split_game(line: &str) -> Result<(&str, &str)> {
+    line.split_once(' ').context("Failed to split the line")
+}
+
 fn score_tournament(
-    f: File,
+    games: Lines<'_>,
     parse_game: impl Fn(&str, &str) -> Result<(Move, Outcome)>,
 ) -> Result<u64> {
-    let reader = BufReader::new(f);
+    games
+        .parse(|line| {
+            let (a, b) = split_game(line)?;
+            let (my_move, outcome) = parse_game(a, b)?;
+            Ok(my_move.score() + outcome.score())
+        })
+        .sum()
+}
 
-    let mut sum = 0;
+pub struct Day02;
 
-    for l in reader.lines() {
-        let line = l.context("Failed to read line")?;
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-        let (a, b) = line.split_once(' ').context("Failed to split the line")?;
-        let (my_move, outcome) = parse_game(a, b)?;
-        sum += my_move.score() + outcome.score();
-    }
-    Ok(sum)
-}
+impl Solution for Day02 {
+    // Second column is interpreted differently in each part, so the lines are only checked
+    type Input<'input> = Lines<'input>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-fn part1(f: File) -> Result<u64> {
-    score_tournament(f, |opp: &str, me: &str| {
-        let (opp, me) = (opp.parse::<Move>()?, me.parse::<Move>()?);
-        Ok((me, Outcome::from((me, opp))))
-    })
-}
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let lines = Lines::new(input);
+        lines
+            .parse(split_game)
+            .try_for_each(|game| game.map(drop))?;
+        Ok(lines)
+    }
 
-fn part2(f: File) -> Result<u64> {
-    score_tournament(f, |opp: &str, outcome: &str| {
-        let (opp, outcome) = (opp.parse::<Move>()?, outcome.parse::<Outcome>()?);
-        let me = Move::from((opp, outcome));
-        Ok((me, outcome))
-    })
-}
+    fn part1(input: &Self::Input<'_>) -> Result<u64> {
+        score_tournament(*input, |opp: &str, me: &str| {
+            let (opp, me) = (opp.parse::<Move>()?, me.parse::<Move>()?);
+            Ok((me, Outcome::from((me, opp))))
+        })
+    }
 
-fn main() -> Result<()> {
-    println!("Score pt1: {}", part1(open!("input.txt")?)?);
-    println!("Score pt2: {}", part2(open!("input.txt")?)?);
-    Ok(())
+    fn part2(input: &Self::Input<'_>) -> Result<u64> {
+        score_tournament(*input, |opp: &str, outcome: &str| {
+            let (opp, outcome) = (opp.parse::<Move>()?, outcome.parse::<Outcome>()?);
+            let me = Move::from((opp, outcome));
+            Ok((me, outcome))
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::read;
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(open!("test.txt").unwrap()).unwrap(), 15);
-    }
-
-    #[test]
-    fn test_part1_regression() {
-        assert_eq!(part1(open!("input.txt").unwrap()).unwrap(), 15523);
+        assert_eq!(Day02::solve_part1(&read!("test.txt").unwrap()).unwrap(), 15);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(open!("test.txt").unwrap()).unwrap(), 12);
+        assert_eq!(Day02::solve_part2(&read!("test.txt").unwrap()).unwrap(), 12);
     }
 
     #[test]
-    fn test_part2_regression() {
-        assert_eq!(part2(open!("input.txt").unwrap()).unwrap(), 15702);
+    fn test_inline() {
+        // rock vs paper: 2 + 6, then draw on scissors: 3 + 3
+        assert_eq!(Day02::solve_part1("A Y\n\nC Z\n").unwrap(), 14);
+        assert_eq!(Day02::solve_part2("A Y\nC Z").unwrap(), 4 + 7);
+        assert!(Day02::solve_part1("A W").is_err());
+        assert!(Day02::parse("AY").is_err());
+        let err = format!("{:#}", Day02::solve_part2("A Y\n\nB W\n").unwrap_err());
+        assert!(err.starts_with("Line 3: 'B W': "), "{err}");
     }
 }
@@ -0,0 +1,105 @@
+use crate::{
+    lines::{at_line, Lines},
+    Solution,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::HashMap;
+
+fn gen_priority_map() -> HashMap<char, usize> {
+    ('a'..='z')
+        .chain('A'..='Z')
+        .enumerate()
+        .map(|(i, c)| (c, i + 1))
+        .collect::<HashMap<_, _>>()
+}
+
+/// Bitset of the item priorities
+fn items(map: &HashMap<char, usize>, items: &str) -> Result<u64> {
+    items.chars().try_fold(0, |set, c| {
+        Ok(set | 1 << map.get(&c).with_context(|| anyhow!("Incorrect item {c}"))?)
+    })
+}
+
+/// Priority of the item that's in every set
+fn common_priority(sets: impl IntoIterator<Item = u64>) -> Result<usize> {
+    match sets.into_iter().fold(u64::MAX, |common, set| common & set) {
+        0 => bail!("No common items"),
+        common => Ok(common.trailing_zeros().try_into()?),
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'input> = Lines<'input>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(Lines::new(input))
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<usize> {
+        let map = gen_priority_map();
+        input
+            .parse(|line| {
+                // Items are ASCII letters, so the length in bytes is the number of items
+                if let Some(c) = line.chars().find(|c| !c.is_ascii_alphabetic()) {
+                    bail!("Incorrect item {c}");
+                }
+                if line.len() % 2 != 0 {
+                    bail!("Odd number of items in line");
+                }
+                let (l, r) = line.split_at(line.len() / 2);
+                common_priority([items(&map, l)?, items(&map, r)?])
+            })
+            .sum()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<usize> {
+        let map = gen_priority_map();
+        input
+            .chunks::<3>()
+            .map(|group| {
+                let group = group?;
+                let sets = group
+                    .map(|rec| at_line(rec, items(&map, rec.1)))
+                    .into_iter()
+                    .collect::<Result<Vec<_>>>()?;
+                common_priority(sets)
+                    .with_context(|| format!("Lines {}-{}", group[0].0, group[2].0))
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day03::solve_part1(&read!("test.txt").unwrap()).unwrap(),
+            157
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day03::solve_part2(&read!("test.txt").unwrap()).unwrap(), 70);
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = format!("{:#}", Day03::solve_part1("abca\nab-b\n").unwrap_err());
+        assert!(err.starts_with("Line 2: 'ab-b': "), "{err}");
+        let err = format!("{:#}", Day03::solve_part1("abca\naéb\n").unwrap_err());
+        assert_eq!(err, "Line 2: 'aéb': Incorrect item é");
+        let err = format!("{:#}", Day03::solve_part2("ab\ncd\nef\nab").unwrap_err());
+        assert!(err.starts_with("Lines 1-3: No common items"), "{err}");
+        let err = format!("{:#}", Day03::solve_part2("ab\nab\nab\nab").unwrap_err());
+        assert!(err.contains("starting at line 4"), "{err}");
+    }
+}
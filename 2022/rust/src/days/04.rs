@@ -0,0 +1,75 @@
+use crate::{interval::Interval, lines::Lines, scan, Solution};
+use anyhow::Result;
+
+fn parse_line(s: &str) -> Result<Pair> {
+    let (a, b, c, d) = scan!("{}-{},{}-{}", s => u64, u64, u64, u64)?;
+    Ok((Interval::new(a, b)?, Interval::new(c, d)?))
+}
+
+type Pair = (Interval<u64>, Interval<u64>);
+
+fn count(pairs: &[Pair], should_count: impl Fn(&Interval<u64>, &Interval<u64>) -> bool) -> u64 {
+    let mut count = 0;
+    for (a, b) in pairs {
+        if should_count(a, b) {
+            count += 1;
+        }
+    }
+    count
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'input> = Vec<Pair>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Lines::new(input).parse(parse_line).collect()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<u64> {
+        Ok(count(input, |a, b| {
+            a.contains_interval(b) || b.contains_interval(a)
+        }))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<u64> {
+        Ok(count(input, Interval::overlaps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day04::solve_part1(&read!("test.txt").unwrap()).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day04::solve_part2(&read!("test.txt").unwrap()).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_bad_line() {
+        let err = format!("{:#}", Day04::parse("2-4,6-8\n1-2,3-x").unwrap_err());
+        assert!(
+            err.contains("Line 2: '1-2,3-x': Field 4 ('x') is not a valid u64"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_bad_range() {
+        let err = format!("{:#}", Day04::parse("2-4,6-8\n5-3,1-2").unwrap_err());
+        assert!(
+            err.contains("Line 2: '5-3,1-2': Interval 5-3 ends before it starts"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    parsers::{lines_of, spaced, uint},
+    NomFinish, Pres, Solution,
+};
+use anyhow::{bail, Context, Result};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{anychar, char as nchar, space0},
+    combinator::{map, map_res},
+    error::context,
+    multi::separated_list1,
+    sequence::{delimited, preceded, separated_pair, terminated, tuple},
+};
+
+fn one_crate(input: &str) -> Pres<'_, Option<char>> {
+    // "[a]" or "   "
+    context(
+        "crate",
+        alt((
+            map(delimited(nchar('['), anychar, nchar(']')), Some),
+            map(tag("   "), |_| None),
+        )),
+    )(input)
+}
+
+fn stacks(input: &str) -> Pres<'_, Vec<Vec<Option<char>>>> {
+    //     [b]
+    // [c] [d]
+    separated_list1(nchar('\n'), separated_list1(nchar(' '), one_crate))(input)
+}
+
+fn finalized_stacks(input: &str) -> Pres<'_, Vec<Vec<char>>> {
+    //     [b]
+    // [c] [d]
+    //  1   2
+    map_res(
+        terminated(
+            terminated(context("stacks", stacks), nchar('\n')),
+            context(
+                "stack numbers",
+                tuple((
+                    delimited(space0, spaced(uint::<usize>), space0),
+                    nchar('\n'),
+                )),
+            ),
+        ),
+        |v| -> Result<_> {
+            // need to rotate vecs
+            let mut res = vec![Vec::<char>::new(); v.last().map_or(0, Vec::len)];
+            for row in v.into_iter().rev() {
+                for (i, c) in row
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(i, c)| c.map(|c| (i, c)))
+                {
+                    res.get_mut(i)
+                        .map(|v| v.push(c))
+                        .context("Line wider than base")?;
+                }
+            }
+            Ok(res)
+        },
+    )(input)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Move {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+fn one_move(input: &str) -> Pres<'_, Move> {
+    context(
+        "move",
+        map(
+            tuple((
+                preceded(tag("move "), uint),
+                preceded(tag(" from "), uint),
+                preceded(tag(" to "), uint),
+            )),
+            |(count, from, to)| Move { count, from, to },
+        ),
+    )(input)
+}
+
+pub struct Cargo {
+    stacks: Vec<Vec<char>>,
+    moves: Vec<Move>,
+}
+
+fn rearrange(cargo: &Cargo, should_reverse: bool) -> Result<String> {
+    let mut s = cargo.stacks.clone();
+    for &Move { count, from, to } in &cargo.moves {
+        // Stacks are numbered from 1
+        let stack = |n: usize| n.checked_sub(1).filter(|&i| i < s.len());
+        let from = stack(from).context("wrong 'from'")?;
+        let to = stack(to).context("wrong 'to'")?;
+        let source = s.get_mut(from).context("wrong 'from'")?;
+        if count > source.len() {
+            bail!("Can't take more than in stack");
+        }
+        if from == to {
+            // Noop
+            continue;
+        }
+        let split = source.split_off(source.len() - count);
+        let dest = s.get_mut(to).context("wrong 'to'")?;
+        if should_reverse {
+            dest.extend(split.into_iter().rev());
+        } else {
+            dest.extend_from_slice(&split);
+        }
+    }
+    Ok(s.into_iter().map(|s| *s.last().unwrap_or(&' ')).collect())
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input<'input> = Cargo;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        map(
+            separated_pair(finalized_stacks, nchar('\n'), lines_of(one_move)),
+            |(stacks, moves)| Cargo { stacks, moves },
+        )(input)
+        .finish_all(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<String> {
+        rearrange(input, true)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<String> {
+        rearrange(input, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read;
+    #[test]
+    fn nom_tests() {
+        assert_eq!(one_crate("[a]"), Ok(("", Some('a'))));
+        assert_eq!(one_crate("   "), Ok(("", None)));
+        assert_eq!(
+            stacks("    [b]\n[c] [d]"),
+            Ok(("", vec![vec![None, Some('b')], vec![Some('c'), Some('d')]]))
+        );
+        assert_eq!(
+            finalized_stacks("    [b]\n[c] [d]\n 1   2\n"),
+            Ok(("", vec![vec!['c'], vec!['d', 'b']]))
+        );
+
+        assert_eq!(
+            one_move("move 1 from 2 to 1"),
+            Ok((
+                "",
+                Move {
+                    count: 1,
+                    from: 2,
+                    to: 1
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day05::solve_part1(&read!("test.txt").unwrap()).unwrap(),
+            "CMZ"
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Day05::solve_part2(&read!("test.txt").unwrap()).unwrap(),
+            "MCD"
+        );
+    }
+
+    #[test]
+    fn test_bad_stack() {
+        let stacks = "[A] [B]\n 1   2\n\n";
+        for (moves, err) in [
+            ("move 1 from 0 to 1", "wrong 'from'"),
+            ("move 1 from 3 to 1", "wrong 'from'"),
+            ("move 1 from 1 to 0", "wrong 'to'"),
+            ("move 5 from 0 to 0", "wrong 'from'"),
+            ("move 1 from 7 to 7", "wrong 'from'"),
+            ("move 2 from 1 to 1", "Can't take more than in stack"),
+        ] {
+            let res = Day05::solve_part1(&format!("{stacks}{moves}\n"));
+            assert_eq!(res.unwrap_err().to_string(), err, "{moves}");
+        }
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let input = "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\nmove 1 form 1 to 2\n";
+        let err = Day05::parse(input).err().unwrap();
+        let d = err.downcast_ref::<crate::diagnostic::Diagnostic>().unwrap();
+        assert_eq!((d.line, d.column), (6, 1));
+        assert_eq!(d.snippet, "move 1 form 1 to 2");
+        assert_eq!(
+            d.message,
+            "parsing stopped here, 19 bytes (1 line) left unparsed"
+        );
+        // Only a single trailing newline is allowed
+        assert!(Day05::parse(&format!("{input}\n")).is_err());
+
+        let err = Day05::parse("[N} [C]\n 1   2\n").err().unwrap();
+        let err = format!("{err:#}");
+        assert!(err.contains("1 | [N} [C]\n  | ^"), "{err}");
+        assert!(err.contains("while parsing stacks at 1:1"), "{err}");
+    }
+}
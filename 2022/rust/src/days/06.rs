@@ -1,15 +1,8 @@
-#![allow(dead_code)]
-
+use crate::{AltRun, Alternative, Part, Solution};
 use anyhow::{Context, Result};
-use aoc::open;
 use itertools::Itertools;
 
-use std::{
-    fs::File,
-    io::{prelude::*, BufReader},
-};
-
-// TODO: Run benchmarks on these 3 approaches
+// 3 approaches, parse_ring* are benchmarked as alternatives (`aoc bench 06`)
 fn parse_ring_unsafe<const WINDOW: usize>(input: &str) -> Result<usize> {
     #![allow(clippy::undocumented_unsafe_blocks)]
 
@@ -95,22 +88,49 @@ fn parse<const WINDOW: usize>(input: &str) -> Result<usize> {
         .context("Not enough items in collection")
 }
 
-fn part1(f: File) -> Result<usize> {
-    let mut input = String::new();
-    BufReader::new(f).read_to_string(&mut input)?;
-    parse::<4>(&input)
-}
+pub struct Day06;
 
-fn part2(f: File) -> Result<usize> {
-    let mut input = String::new();
-    BufReader::new(f).read_to_string(&mut input)?;
-    parse::<14>(&input)
-}
+impl Solution for Day06 {
+    type Input<'input> = &'input str;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-fn main() -> Result<()> {
-    println!("Part 1: {}", part1(open!("input.txt")?)?);
-    println!("Part 2: {}", part2(open!("input.txt")?)?);
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<usize> {
+        parse::<4>(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<usize> {
+        parse::<14>(input)
+    }
+
+    fn alternatives() -> Vec<Alternative<Self>> {
+        vec![
+            Alternative {
+                name: "ring",
+                part: Part::One,
+                run: AltRun::Parsed(|input| parse_ring::<4>(input).map(|r| r.to_string())),
+            },
+            Alternative {
+                name: "ring_unsafe",
+                part: Part::One,
+                run: AltRun::Parsed(|input| parse_ring_unsafe::<4>(input).map(|r| r.to_string())),
+            },
+            Alternative {
+                name: "ring",
+                part: Part::Two,
+                run: AltRun::Parsed(|input| parse_ring::<14>(input).map(|r| r.to_string())),
+            },
+            Alternative {
+                name: "ring_unsafe",
+                part: Part::Two,
+                run: AltRun::Parsed(|input| parse_ring_unsafe::<14>(input).map(|r| r.to_string())),
+            },
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -136,14 +156,4 @@ mod tests {
             assert_eq!(parse_ring_unsafe::<14>(input).unwrap(), r2);
         }
     }
-
-    #[test]
-    fn test_part1_regression() {
-        assert_eq!(part1(open!("input.txt").unwrap()).unwrap(), 1343);
-    }
-
-    #[test]
-    fn test_part2_regression() {
-        assert_eq!(part2(open!("input.txt").unwrap()).unwrap(), 2193);
-    }
 }
@@ -0,0 +1,866 @@
+#![allow(clippy::indexing_slicing)]
+// indexes are internally tracked, no nodes will be deleted, so it's safe
+
+use crate::{diagnostic::Diagnostic, parsers::uint, NomFinish, Pres, Solution};
+use anyhow::{bail, Result};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{is_not, tag},
+    character::complete::char as nchar,
+    combinator::{consumed, eof, map, not, opt},
+    error::context,
+    multi::many0,
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
+};
+use serde::{Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug)]
+enum Command<'input> {
+    Cd(Vec<Step<'input>>),
+    Ls(Vec<Entry<'input>>),
+    /// The whole command line
+    Unknown(&'input str),
+}
+
+/// Part of a `cd` path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step<'input> {
+    Root,
+    Up,
+    Down(&'input str),
+}
+
+/// `/a/../b/` is `[Root, Down("a"), Up, Down("b")]`, empty and `.` segments are skipped
+fn cd_path(path: &str) -> Vec<Step<'_>> {
+    let root = path.starts_with('/').then_some(Step::Root);
+    root.into_iter()
+        .chain(path.split('/').filter_map(|segment| match segment {
+            "" | "." => None,
+            ".." => Some(Step::Up),
+            name => Some(Step::Down(name)),
+        }))
+        .collect()
+}
+
+/// Line of `ls` output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Entry<'input> {
+    Dir(&'input str),
+    File(&'input str, usize),
+}
+
+impl<'input> Entry<'input> {
+    fn name(self) -> &'input str {
+        match self {
+            Entry::Dir(name) | Entry::File(name, _) => name,
+        }
+    }
+}
+
+type NodeID = usize;
+
+/// Sizes used by the [`Tree`] queries, [`Default`] has the ones from the puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    pub disk_size: usize,
+    pub needed_space: usize,
+    /// Largest dir that counts as small in part 1
+    pub small_dir_limit: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            disk_size: 70_000_000,
+            needed_space: 30_000_000,
+            small_dir_limit: 100_000,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Node<'input> {
+    name: &'input str,
+    // map dirname => node id
+    children: BTreeMap<&'input str, NodeID>,
+    // map filename => size
+    files: BTreeMap<&'input str, usize>,
+    // size of files in this dir
+    size: usize,
+    // `ls` was run here, so `children` and `files` are complete
+    listed: bool,
+    // size of files in children + this dir, filled in when all the commands are handled
+    total_size: usize,
+}
+
+#[derive(Debug)]
+pub struct Tree<'input> {
+    // allocation for tree nodes
+    nodes: Vec<Node<'input>>,
+    // current path
+    path: Vec<NodeID>,
+}
+
+/// Directory of the [`Tree`]
+#[derive(Debug, Clone, Copy)]
+pub struct Dir<'tree, 'input> {
+    tree: &'tree Tree<'input>,
+    id: NodeID,
+}
+
+impl<'tree, 'input> Dir<'tree, 'input> {
+    fn node(self) -> &'tree Node<'input> {
+        &self.tree.nodes[self.id]
+    }
+
+    /// Empty for the root
+    pub fn name(self) -> &'input str {
+        self.node().name
+    }
+
+    /// `ls` was run in the directory, otherwise we only know it exists
+    pub fn is_listed(self) -> bool {
+        self.node().listed
+    }
+
+    /// Size of the files directly in this directory
+    pub fn size(self) -> usize {
+        self.node().size
+    }
+
+    /// Size of all the files in this directory and its subdirectories
+    pub fn total_size(self) -> usize {
+        self.node().total_size
+    }
+
+    /// Names and sizes of the files, sorted by name
+    pub fn files(self) -> impl Iterator<Item = (&'input str, usize)> + use<'tree, 'input> {
+        self.node().files.iter().map(|(&name, &size)| (name, size))
+    }
+
+    /// Subdirectories, sorted by name
+    pub fn dirs(self) -> impl Iterator<Item = Dir<'tree, 'input>> + use<'tree, 'input> {
+        let tree = self.tree;
+        self.node()
+            .children
+            .values()
+            .map(move |&id| Dir { tree, id })
+    }
+
+    pub fn file(self, name: &str) -> Option<usize> {
+        self.node().files.get(name).copied()
+    }
+
+    pub fn dir(self, name: &str) -> Option<Self> {
+        let id = *self.node().children.get(name)?;
+        Some(Dir { id, ..self })
+    }
+}
+
+impl<'input> Tree<'input> {
+    fn new() -> Self {
+        Self {
+            // Initialized with an empty root node
+            nodes: vec![Node::default()],
+            path: Vec::new(),
+        }
+    }
+
+    pub fn root(&self) -> Dir<'_, 'input> {
+        Dir { tree: self, id: 0 }
+    }
+
+    /// Directory by its absolute path, like `/a/e`
+    pub fn get(&self, path: &str) -> Option<Dir<'_, 'input>> {
+        path.strip_prefix('/')?
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(self.root(), Dir::dir)
+    }
+
+    fn handle_command(&mut self, cmd: Command<'input>) -> Result<()> {
+        match cmd {
+            Command::Cd(steps) => {
+                for step in steps {
+                    self.step(step)?;
+                }
+            }
+            Command::Ls(entries) => self.list(&entries)?,
+            Command::Unknown(line) => bail!("Unknown command '{line}', expected cd or ls"),
+        }
+        Ok(())
+    }
+
+    fn step(&mut self, step: Step<'input>) -> Result<()> {
+        match step {
+            Step::Root => {
+                self.path.clear();
+            }
+            Step::Up => {
+                if self.path.pop().is_none() {
+                    bail!("Can't go up from '/'");
+                }
+            }
+            Step::Down(dir_name) => {
+                let cur_dir = self.get_cur_dir();
+                let node_id = match self.nodes[cur_dir].children.get(dir_name) {
+                    Some(&node_id) => node_id,
+                    None if self.nodes[cur_dir].listed => {
+                        bail!("No directory '{dir_name}' in '{}'", self.cur_path())
+                    }
+                    // This is a new name, create a new empty node
+                    None => self.add_dir(cur_dir, dir_name),
+                };
+                self.path.push(node_id);
+            }
+        }
+        Ok(())
+    }
+
+    fn add_dir(&mut self, parent: NodeID, name: &'input str) -> NodeID {
+        let node_id = self.nodes.len();
+        self.nodes.push(Node {
+            name,
+            ..Node::default()
+        });
+        self.nodes[parent].children.insert(name, node_id);
+        node_id
+    }
+
+    /// Fills the current directory from the `ls` output, a repeated listing must be the same
+    fn list(&mut self, entries: &[Entry<'input>]) -> Result<()> {
+        let mut listing = HashMap::new();
+        for &entry in entries {
+            if matches!(entry.name(), "." | "..") || entry.name().contains('/') {
+                bail!(
+                    "'{}' can't be a name of a file or dir in '{}'",
+                    entry.name(),
+                    self.cur_path()
+                );
+            }
+            if listing.insert(entry.name(), entry).is_some() {
+                bail!(
+                    "'{}' is listed twice in '{}'",
+                    entry.name(),
+                    self.cur_path()
+                );
+            }
+        }
+        let cur_dir = self.get_cur_dir();
+        let node = &self.nodes[cur_dir];
+        if node.listed {
+            let same = node.children.len() + node.files.len() == listing.len()
+                && listing.values().all(|entry| match *entry {
+                    Entry::Dir(name) => node.children.contains_key(name),
+                    Entry::File(name, size) => node.files.get(name) == Some(&size),
+                });
+            if !same {
+                bail!(
+                    "Listing of '{}' contradicts the previous one",
+                    self.cur_path()
+                );
+            }
+            return Ok(());
+        }
+        if let Some(name) = node
+            .children
+            .keys()
+            .find(|name| !matches!(listing.get(*name), Some(Entry::Dir(_))))
+        {
+            bail!(
+                "'{name}' was visited, but it's not a directory in the listing of '{}'",
+                self.cur_path()
+            );
+        }
+        for entry in listing.into_values() {
+            match entry {
+                Entry::Dir(name) => {
+                    if !self.nodes[cur_dir].children.contains_key(name) {
+                        self.add_dir(cur_dir, name);
+                    }
+                }
+                Entry::File(name, size) => {
+                    let node = &mut self.nodes[cur_dir];
+                    node.files.insert(name, size);
+                    node.size += size;
+                }
+            }
+        }
+        self.nodes[cur_dir].listed = true;
+        Ok(())
+    }
+
+    fn get_cur_dir(&self) -> NodeID {
+        // If path is empty - we're at root
+        self.path.last().copied().unwrap_or(0)
+    }
+
+    fn cur_path(&self) -> String {
+        if self.path.is_empty() {
+            return "/".to_owned();
+        }
+        self.path
+            .iter()
+            .map(|&idx| format!("/{}", self.nodes[idx].name))
+            .collect()
+    }
+
+    /// Fills in `total_size` of every node in one post-order pass, without recursion,
+    /// so that deep trees don't overflow the stack
+    fn compute_total_sizes(&mut self) {
+        // (node id, its children are already done)
+        let mut stack = vec![(0, false)];
+        while let Some((idx, children_done)) = stack.pop() {
+            if children_done {
+                let node = &self.nodes[idx];
+                let total_size = node.children.values().fold(node.size, |size, &child_idx| {
+                    size + self.nodes[child_idx].total_size
+                });
+                self.nodes[idx].total_size = total_size;
+            } else {
+                stack.push((idx, true));
+                stack.extend(self.nodes[idx].children.values().map(|&idx| (idx, false)));
+            }
+        }
+    }
+
+    fn total_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.nodes.iter().map(|node| node.total_size)
+    }
+
+    /// Sum of the total sizes of the dirs that are at most `params.small_dir_limit`
+    pub fn sum_small_dirs(&self, params: &Params) -> usize {
+        self.total_sizes()
+            .filter(|&size| size <= params.small_dir_limit)
+            .sum()
+    }
+
+    /// Size of the smallest dir that frees `params.needed_space` on the disk when deleted
+    pub fn dir_to_delete(&self, params: &Params) -> Result<usize> {
+        let &Params {
+            disk_size,
+            needed_space,
+            ..
+        } = params;
+        let used_space = self.nodes[0].total_size;
+        if disk_size < used_space {
+            bail!("{used_space} is used, it doesn't fit on a disk of {disk_size}");
+        }
+        let free_space = disk_size - used_space;
+        if free_space >= needed_space {
+            bail!("{free_space} is already free, nothing to delete to get {needed_space}");
+        }
+        let space_to_free = needed_space - free_space;
+        if used_space < space_to_free {
+            bail!("Can't get {needed_space} free on a disk of {disk_size}");
+        }
+
+        Ok(self
+            .total_sizes()
+            .filter(|&size| size >= space_to_free)
+            .min()
+            // the root is big enough, checked above
+            .unwrap_or(used_space))
+    }
+}
+
+/// Entry of a directory listing in the `tree` view
+enum Item<'tree, 'input> {
+    Dir(Dir<'tree, 'input>),
+    File(&'input str, usize),
+}
+
+/// Pending line of the `tree` view
+struct Frame<'tree, 'input> {
+    /// Drawn before the branch of this line
+    indent: String,
+    /// The item is the last in its dir
+    is_last: bool,
+    /// Levels below the root
+    depth: usize,
+    item: Item<'tree, 'input>,
+}
+
+impl<'tree, 'input> Dir<'tree, 'input> {
+    /// Files and subdirectories together, sorted by name
+    fn items(self) -> Vec<(&'input str, Item<'tree, 'input>)> {
+        let mut items: Vec<_> = self
+            .dirs()
+            .map(|dir| (dir.name(), Item::Dir(dir)))
+            .chain(
+                self.files()
+                    .map(|(name, size)| (name, Item::File(name, size))),
+            )
+            .collect();
+        items.sort_unstable_by_key(|&(name, _)| name);
+        items
+    }
+
+    /// Pushes the items for the `tree` view in the reverse order, so that they're popped sorted
+    fn push_items(self, stack: &mut Vec<Frame<'tree, 'input>>, indent: &str, depth: usize) {
+        let items = self.items();
+        let last = items.len().saturating_sub(1);
+        for (i, (_, item)) in items.into_iter().enumerate().rev() {
+            stack.push(Frame {
+                indent: indent.to_owned(),
+                is_last: i == last,
+                depth,
+                item,
+            });
+        }
+    }
+}
+
+/// `du -h` style size: bytes below 1K, otherwise rounded up to 2 significant digits with a unit
+fn human_size(size: usize) -> String {
+    let size = u128::try_from(size).unwrap_or(u128::MAX);
+    let Some((unit, name)) = [
+        (1 << 40, 'T'),
+        (1 << 30, 'G'),
+        (1 << 20, 'M'),
+        (1 << 10, 'K'),
+    ]
+    .into_iter()
+    .find(|&(unit, _)| size >= unit) else {
+        return size.to_string();
+    };
+    match (size * 10).div_ceil(unit) {
+        tenths if tenths < 100 => format!("{}.{}{name}", tenths / 10, tenths % 10),
+        _ => format!("{}{name}", size.div_ceil(unit)),
+    }
+}
+
+impl Tree<'_> {
+    /// Like `tree --du`: every entry with its (total) size, dirs end with `/`.
+    /// `max_depth` limits the levels below the root, like `tree -L`.
+    pub fn render_tree(&self, max_depth: Option<usize>) -> String {
+        let mut out = format!("/ ({})\n", self.root().total_size());
+        // Popped in the output order
+        let mut stack = Vec::new();
+        if max_depth != Some(0) {
+            self.root().push_items(&mut stack, "", 1);
+        }
+        while let Some(Frame {
+            indent: prefix,
+            is_last,
+            depth,
+            item,
+        }) = stack.pop()
+        {
+            let branch = if is_last { "└── " } else { "├── " };
+            match item {
+                Item::File(name, size) => {
+                    out.push_str(&format!("{prefix}{branch}{name} ({size})\n"))
+                }
+                Item::Dir(dir) => {
+                    out.push_str(&format!(
+                        "{prefix}{branch}{}/ ({})\n",
+                        dir.name(),
+                        dir.total_size()
+                    ));
+                    if max_depth.is_none_or(|max| depth < max) {
+                        let indent = if is_last { "    " } else { "│   " };
+                        dir.push_items(&mut stack, &format!("{prefix}{indent}"), depth + 1);
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Like `du -h`: total size and path of every dir, subdirs before their parent
+    pub fn du(&self) -> String {
+        // Pre-order with the subdirs in the reverse order, reversed into the post-order
+        let mut lines = Vec::new();
+        let mut stack = vec![(self.root(), String::new())];
+        while let Some((dir, path)) = stack.pop() {
+            for sub in dir.dirs() {
+                stack.push((sub, format!("{path}/{}", sub.name())));
+            }
+            let path = if path.is_empty() {
+                "/".to_owned()
+            } else {
+                path
+            };
+            lines.push(format!("{}\t{path}\n", human_size(dir.total_size())));
+        }
+        lines.into_iter().rev().collect()
+    }
+}
+
+/// Directory in the JSON export
+#[derive(Serialize)]
+struct JsonDir<'tree, 'input> {
+    /// Index in the list
+    id: usize,
+    /// Index of the parent dir, `None` for the root
+    parent: Option<usize>,
+    name: &'input str,
+    /// Total size
+    size: usize,
+    listed: bool,
+    files: &'tree BTreeMap<&'input str, usize>,
+}
+
+/// Flat list of the dirs in pre-order, sorted by name: nested JSON as deep as the tree
+/// would overflow the stack of both the writer and the readers
+impl Serialize for Tree<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut dirs = Vec::with_capacity(self.nodes.len());
+        let mut stack = vec![(self.root(), None)];
+        while let Some((dir, parent)) = stack.pop() {
+            let id = dirs.len();
+            dirs.push(JsonDir {
+                id,
+                parent,
+                name: dir.name(),
+                size: dir.total_size(),
+                listed: dir.is_listed(),
+                files: &dir.node().files,
+            });
+            let subdirs: Vec<_> = dir.dirs().collect();
+            stack.extend(subdirs.into_iter().rev().map(|sub| (sub, Some(id))));
+        }
+        serializer.collect_seq(dirs)
+    }
+}
+
+fn ls_entry(input: &str) -> Pres<'_, Entry<'_>> {
+    context(
+        "ls output",
+        alt((
+            map(preceded(tag("dir "), is_not("\n")), Entry::Dir),
+            map(
+                separated_pair(uint, nchar(' '), is_not("\n")),
+                |(size, name)| Entry::File(name, size),
+            ),
+        )),
+    )(input)
+}
+
+fn parse_ls_output(input: &str) -> Pres<'_, Vec<Entry<'_>>> {
+    many0(terminated(ls_entry, opt(nchar('\n'))))(input)
+}
+
+/// Lines up to the next command
+fn skip_output(input: &str) -> Pres<'_, ()> {
+    map(
+        many0(preceded(
+            nchar('\n'),
+            preceded(not(nchar('$')), is_not("\n")),
+        )),
+        |_| (),
+    )(input)
+}
+
+/// Commands with their text, to point at the one that doesn't fit the tree
+fn parse(input: &str) -> Pres<'_, Vec<(&str, Command<'_>)>> {
+    many0(consumed(delimited(
+        tag("$ "),
+        context(
+            "command",
+            alt((
+                map(preceded(tag("cd "), is_not("\n")), |path: &str| {
+                    Command::Cd(cd_path(path.trim()))
+                }),
+                map(
+                    preceded(pair(tag("ls"), alt((tag("\n"), eof))), parse_ls_output),
+                    Command::Ls,
+                ),
+                // Reported when the tree is built, the output can't be checked anyway
+                map(terminated(is_not("\n"), skip_output), Command::Unknown),
+            )),
+        ),
+        opt(nchar('\n')),
+    )))(input)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input<'input> = Tree<'input>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let mut tree = Tree::new();
+        for (text, cmd) in parse(input).finish_all(input)? {
+            tree.handle_command(cmd)
+                .map_err(|e| Diagnostic::new(input, text, format!("{e:#}")))?;
+        }
+        tree.compute_total_sizes();
+        Ok(tree)
+    }
+
+    fn part1(tree: &Self::Input<'_>) -> Result<usize> {
+        Ok(tree.sum_small_dirs(&Params::default()))
+    }
+
+    fn part2(tree: &Self::Input<'_>) -> Result<usize> {
+        tree.dir_to_delete(&Params::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day07::solve_part1(&read!("test.txt").unwrap()).unwrap(),
+            95437
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Day07::solve_part2(&read!("test.txt").unwrap()).unwrap(),
+            24933642
+        );
+    }
+
+    #[test]
+    fn test_params() {
+        // / = 60 (a = 30 (b = 10, x = 20), c = 30)
+        let tree =
+            Day07::parse("$ ls\ndir a\n30 c\n$ cd a\n$ ls\ndir b\n20 x\n$ cd b\n$ ls\n10 y\n")
+                .unwrap();
+        let params = |disk_size, needed_space, small_dir_limit| Params {
+            disk_size,
+            needed_space,
+            small_dir_limit,
+        };
+        assert_eq!(tree.sum_small_dirs(&params(0, 0, 9)), 0);
+        assert_eq!(tree.sum_small_dirs(&params(0, 0, 30)), 10 + 30);
+        assert_eq!(tree.sum_small_dirs(&params(0, 0, 60)), 10 + 30 + 60);
+
+        assert_eq!(tree.dir_to_delete(&params(100, 41, 0)).unwrap(), 10);
+        // Frees exactly what's needed
+        assert_eq!(tree.dir_to_delete(&params(100, 50, 0)).unwrap(), 10);
+        assert_eq!(tree.dir_to_delete(&params(100, 51, 0)).unwrap(), 30);
+        assert_eq!(tree.dir_to_delete(&params(100, 71, 0)).unwrap(), 60);
+        // The whole disk
+        assert_eq!(tree.dir_to_delete(&params(60, 60, 0)).unwrap(), 60);
+
+        let err = |params: Params| tree.dir_to_delete(&params).unwrap_err().to_string();
+        assert_eq!(
+            err(params(59, 1, 0)),
+            "60 is used, it doesn't fit on a disk of 59"
+        );
+        assert_eq!(
+            err(params(100, 40, 0)),
+            "40 is already free, nothing to delete to get 40"
+        );
+        assert_eq!(
+            err(params(100, 101, 0)),
+            "Can't get 101 free on a disk of 100"
+        );
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        // /d/d/.../d, every dir has a file of size 1
+        const DEPTH: usize = 100_000;
+        let input = "$ ls\n1 f\ndir d\n$ cd d\n".repeat(DEPTH) + "$ ls\n1 f\n";
+        let tree = Day07::parse(&input).unwrap();
+        assert_eq!(tree.root().total_size(), DEPTH + 1);
+        // Dirs have all the sizes from 1 to DEPTH + 1
+        let limit = Params::default().small_dir_limit;
+        assert_eq!(Day07::part1(&tree).unwrap(), limit * (limit + 1) / 2);
+        let params = Params {
+            disk_size: DEPTH + 1,
+            needed_space: 500,
+            ..Params::default()
+        };
+        assert_eq!(tree.dir_to_delete(&params).unwrap(), 500);
+    }
+
+    #[test]
+    fn test_deep_tree_json() {
+        const DEPTH: usize = 100_000;
+        let input = "$ ls\n1 f\ndir d\n$ cd d\n".repeat(DEPTH) + "$ ls\n1 f\n";
+        let tree = Day07::parse(&input).unwrap();
+        let json = serde_json::to_string(&tree).unwrap();
+        let dirs: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(dirs.len(), DEPTH + 1);
+        assert_eq!(
+            dirs[DEPTH],
+            serde_json::json!({
+                "id": DEPTH, "parent": DEPTH - 1, "name": "d", "size": 1, "listed": true,
+                "files": {"f": 1},
+            })
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        let input = read!("test.txt").unwrap();
+        let tree = Day07::parse(&input).unwrap();
+        let e = tree.get("/a/e").unwrap();
+        assert_eq!(e.name(), "e");
+        assert_eq!(e.files().collect::<Vec<_>>(), [("i", 584)]);
+        assert_eq!(e.dirs().count(), 0);
+        let a = tree.get("/a/").unwrap();
+        assert_eq!((a.size(), a.total_size()), (94269, 94853));
+        assert_eq!(a.file("h.lst"), Some(62596));
+        assert_eq!(a.dirs().map(Dir::name).collect::<Vec<_>>(), ["e"]);
+        assert_eq!(tree.get("/").unwrap().total_size(), 48381165);
+        assert!(tree.get("/a/h.lst").is_none());
+        assert!(tree.get("a").is_none());
+    }
+
+    #[test]
+    fn test_listings() {
+        let parse = |input: &str| Day07::parse(input).map(|tree| tree.root().total_size());
+        let err = |input: &str| format!("{:#}", Day07::parse(input).err().unwrap());
+
+        // Repeated listings are counted once
+        assert_eq!(
+            parse("$ ls\n1 a\ndir b\n$ cd b\n$ cd ..\n$ ls\ndir b\n1 a\n").unwrap(),
+            1
+        );
+        // Unlisted dirs are fine until the parent is listed
+        assert_eq!(
+            parse("$ cd a\n$ ls\n2 b\n$ cd /\n$ ls\ndir a\n").unwrap(),
+            2
+        );
+
+        let e = err("$ ls\n1 a\n$ ls\n2 a\n");
+        assert!(
+            e.contains("Listing of '/' contradicts the previous one"),
+            "{e}"
+        );
+        assert!(e.contains("3 | $ ls\n  | ^"), "{e}");
+        let e = err("$ ls\n1 a\ndir a\n");
+        assert!(e.contains("'a' is listed twice in '/'"), "{e}");
+        let e = err("$ ls\ndir a\n1 b\n$ cd a\n$ ls\n$ cd b\n");
+        assert!(e.contains("No directory 'b' in '/a'"), "{e}");
+        let e = err("$ ls\ndir a\n1 b\n$ cd b\n");
+        assert!(e.contains("No directory 'b' in '/'"), "{e}");
+        let e = err("$ cd a\n$ cd /\n$ ls\n1 a\n");
+        assert!(
+            e.contains("'a' was visited, but it's not a directory in the listing of '/'"),
+            "{e}"
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let input = read!("test.txt").unwrap();
+        let tree = Day07::parse(&input).unwrap();
+        assert_eq!(
+            tree.render_tree(None),
+            "\
+/ (48381165)
+├── a/ (94853)
+│   ├── e/ (584)
+│   │   └── i (584)
+│   ├── f (29116)
+│   ├── g (2557)
+│   └── h.lst (62596)
+├── b.txt (14848514)
+├── c.dat (8504156)
+└── d/ (24933642)
+    ├── d.ext (5626152)
+    ├── d.log (8033020)
+    ├── j (4060174)
+    └── k (7214296)
+"
+        );
+        assert_eq!(
+            tree.render_tree(Some(1)),
+            "/ (48381165)\n├── a/ (94853)\n├── b.txt (14848514)\n├── c.dat (8504156)\n└── d/ (24933642)\n"
+        );
+        assert_eq!(tree.render_tree(Some(0)), "/ (48381165)\n");
+        assert_eq!(tree.du(), "584\t/a/e\n93K\t/a\n24M\t/d\n47M\t/\n");
+
+        let json = serde_json::to_value(&tree).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {
+                    "id": 0, "parent": null, "name": "", "size": 48381165, "listed": true,
+                    "files": {"b.txt": 14848514, "c.dat": 8504156},
+                },
+                {
+                    "id": 1, "parent": 0, "name": "a", "size": 94853, "listed": true,
+                    "files": {"f": 29116, "g": 2557, "h.lst": 62596},
+                },
+                {"id": 2, "parent": 1, "name": "e", "size": 584, "listed": true, "files": {"i": 584}},
+                {
+                    "id": 3, "parent": 0, "name": "d", "size": 24933642, "listed": true,
+                    "files": {"d.ext": 5626152, "d.log": 8033020, "j": 4060174, "k": 7214296},
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_human_size() {
+        assert_eq!(human_size(1023), "1023");
+        assert_eq!(human_size(1024), "1.0K");
+        assert_eq!(human_size(1025), "1.1K");
+        assert_eq!(human_size(10 * 1024 - 1), "10K");
+        assert_eq!(human_size(3 << 30), "3.0G");
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let input = "$ cd /\n$ ls\n123 a\nb.txt\n$ cd a\n";
+        let err = Day07::parse(input).err().unwrap();
+        let d = err.downcast_ref::<crate::diagnostic::Diagnostic>().unwrap();
+        assert_eq!((d.line, d.column, d.snippet.as_str()), (4, 1, "b.txt"));
+    }
+
+    #[test]
+    fn test_unknown_command() {
+        let input = "$ cd /\n$ ls\n123 a\n$ rm a\n$ ls\n";
+        let err = format!("{:#}", Day07::parse(input).err().unwrap());
+        assert!(
+            err.contains("error: Unknown command 'rm a', expected cd or ls"),
+            "{err}"
+        );
+        assert!(err.contains("4 | $ rm a\n  | ^"), "{err}");
+        // Its output isn't mistaken for a parsing error
+        let err = format!(
+            "{:#}",
+            Day07::parse("$ ls\n$ pwd\n/\n$ ls\n").err().unwrap()
+        );
+        assert!(err.contains("Unknown command 'pwd'"), "{err}");
+    }
+
+    #[test]
+    fn test_cd_paths() {
+        use Step::{Down, Root, Up};
+        assert_eq!(cd_path("/"), [Root]);
+        assert_eq!(cd_path("/a/../b/"), [Root, Down("a"), Up, Down("b")]);
+        assert_eq!(cd_path("../x"), [Up, Down("x")]);
+        assert_eq!(cd_path("./a//b"), [Down("a"), Down("b")]);
+
+        let input = "$ cd /a/e\n$ ls\n1 x\n$ cd ../../b\n$ ls\n2 y\n$ cd /\n$ ls\ndir a\ndir b\n";
+        let tree = Day07::parse(input).unwrap();
+        assert_eq!(tree.get("/a/e").unwrap().file("x"), Some(1));
+        assert_eq!(tree.get("/b").unwrap().file("y"), Some(2));
+        assert_eq!(tree.root().total_size(), 3);
+        // Relative to the current dir
+        let tree = Day07::parse("$ cd a\n$ cd b/c\n$ ls\n1 x\n").unwrap();
+        assert_eq!(tree.get("/a/b/c").unwrap().size(), 1);
+
+        let err = |input: &str| format!("{:#}", Day07::parse(input).err().unwrap());
+        let e = err("$ cd /\n$ cd a\n$ cd ../..\n");
+        assert!(e.contains("error: Can't go up from '/'"), "{e}");
+        assert!(e.contains("3 | $ cd ../..\n  | ^"), "{e}");
+        let e = err("$ ls\ndir a\n$ cd a/../b\n");
+        assert!(e.contains("No directory 'b' in '/'"), "{e}");
+        let e = err("$ ls\ndir a/b\n");
+        assert!(
+            e.contains("'a/b' can't be a name of a file or dir in '/'"),
+            "{e}"
+        );
+    }
+}
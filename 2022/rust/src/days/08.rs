@@ -0,0 +1,142 @@
+use crate::{
+    grid::{Dir, Grid},
+    Solution,
+};
+use anyhow::{Context, Result};
+
+#[derive(Clone)]
+pub struct Tree {
+    visible: bool,
+    height: usize,
+    score: usize,
+}
+
+fn parse(input: &str) -> Result<Grid<Tree>> {
+    Grid::parse(input, |c| {
+        Ok(Tree {
+            visible: false,
+            height: c.to_digit(10).context("Incorrect input")?.try_into()?,
+            score: 1,
+        })
+    })
+}
+
+trait Processor {
+    fn new() -> Self;
+    fn reset(&mut self);
+    fn process(&mut self, t: &mut Tree) -> Result<()>;
+}
+
+struct VisProc(Option<usize>);
+
+impl Processor for VisProc {
+    fn new() -> Self {
+        Self(None)
+    }
+
+    fn reset(&mut self) {
+        *self = Self(None);
+    }
+
+    fn process(&mut self, t: &mut Tree) -> Result<()> {
+        if !matches!(self.0, Some(ph) if t.height <= ph) {
+            t.visible = true;
+            self.0 = Some(t.height);
+        }
+        Ok(())
+    }
+}
+
+struct ScoreProc([usize; 10]);
+
+impl Processor for ScoreProc {
+    fn new() -> Self {
+        Self([0; 10])
+    }
+
+    fn reset(&mut self) {
+        self.0.fill(0);
+    }
+
+    fn process(&mut self, t: &mut Tree) -> Result<()> {
+        t.score *= self
+            .0
+            .get(t.height)
+            .with_context(|| format!("Tree height {} is over 9", t.height))?;
+        for (i, h) in self.0.iter_mut().enumerate() {
+            if i <= t.height {
+                *h = 1;
+            } else {
+                *h += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn process<P: Processor>(grid: &mut Grid<Tree>) -> Result<()> {
+    let mut p = P::new();
+    for dir in Dir::ALL {
+        for line in grid.lines(dir) {
+            p.reset();
+            for pos in line {
+                let tree = grid
+                    .get_mut(pos)
+                    .with_context(|| format!("{pos:?} is outside of the grid"))?;
+                p.process(tree)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input<'input> = Grid<Tree>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<usize> {
+        let mut t = input.clone();
+        process::<VisProc>(&mut t)?;
+        Ok(t.into_values().map(|t| usize::from(t.visible)).sum())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<usize> {
+        let mut t = input.clone();
+        process::<ScoreProc>(&mut t)?;
+        t.into_values().map(|t| t.score).max().context("No max??")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day08::solve_part1(&read!("test.txt").unwrap()).unwrap(), 21);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day08::solve_part2(&read!("test.txt").unwrap()).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_not_square() {
+        let wide = "30373\n25512\n65332\n";
+        let tall = "326\n055\n353\n713\n322\n";
+        for input in [wide, tall] {
+            assert_eq!(Day08::solve_part1(input).unwrap(), 14);
+            assert_eq!(Day08::solve_part2(input).unwrap(), 2);
+        }
+        assert!(Day08::solve_part1("303\n25").is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{bench, solve, solve_alternatives, Part};
+use anyhow::Result;
+
+// Day modules keep their `NN.rs` names so that `open!` can still figure out the day from `file!()`
+#[path = "01.rs"]
+pub mod day01;
+#[path = "02.rs"]
+pub mod day02;
+#[path = "03.rs"]
+pub mod day03;
+#[path = "04.rs"]
+pub mod day04;
+#[path = "05.rs"]
+pub mod day05;
+#[path = "06.rs"]
+pub mod day06;
+#[path = "07.rs"]
+pub mod day07;
+#[path = "08.rs"]
+pub mod day08;
+
+pub type SolveFn = fn(&str, &[Part]) -> Result<Vec<(Part, Result<String>)>>;
+pub type AlternativesFn = fn(&str) -> Result<Vec<(String, Part, Result<String>)>>;
+pub type BenchFn = fn(&str, &bench::Options) -> Result<Vec<bench::Measurement>>;
+
+pub struct Day {
+    pub day: u8,
+    pub solve: SolveFn,
+    pub alternatives: AlternativesFn,
+    pub bench: BenchFn,
+}
+
+/// Calls `$callback!` with the list of all solutions: `1 => path::to::Day01, ...`
+#[macro_export]
+macro_rules! with_days {
+    ($callback:ident) => {
+        $callback! {
+            1 => $crate::days::day01::Day01,
+            2 => $crate::days::day02::Day02,
+            3 => $crate::days::day03::Day03,
+            4 => $crate::days::day04::Day04,
+            5 => $crate::days::day05::Day05,
+            6 => $crate::days::day06::Day06,
+            7 => $crate::days::day07::Day07,
+            8 => $crate::days::day08::Day08,
+        }
+    };
+}
+
+macro_rules! register {
+    ($($day:literal => $solution:path),* $(,)?) => {
+        pub static DAYS: &[Day] = &[$(
+            Day {
+                day: $day,
+                solve: solve::<$solution>,
+                alternatives: solve_alternatives::<$solution>,
+                bench: bench::bench::<$solution>,
+            },
+        )*];
+    };
+}
+
+with_days!(register);
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
@@ -0,0 +1,201 @@
+use std::fmt::{self, Display};
+
+use nom::error::{Error, ErrorKind, VerboseError, VerboseErrorKind};
+
+/// Parse error pointing at the place in the input, rendered like a compiler error:
+///
+/// ```text
+/// error: expected '[', found 'x'
+///  --> 05/test.txt:2:5
+///   |
+/// 2 | [N] x
+///   |     ^
+///   = while parsing stacks at 1:1
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub message: String,
+    /// Not known while parsing, the runner fills it in with [`Diagnostic::set_file`]
+    pub file: Option<String>,
+    /// 1-based
+    pub line: usize,
+    /// 1-based, in characters
+    pub column: usize,
+    /// The whole line with the error
+    pub snippet: String,
+    /// Context labels from the innermost to the outermost one
+    pub notes: Vec<String>,
+}
+
+/// 1-based line and column of the byte offset, with the text of the line
+fn locate(input: &str, offset: usize) -> (usize, usize, &str) {
+    let offset = offset.min(input.len());
+    let before = input.get(..offset).unwrap_or_default();
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line_end = input
+        .get(offset..)
+        .and_then(|rest| rest.find('\n'))
+        .map_or(input.len(), |i| offset + i);
+    let line = before.matches('\n').count() + 1;
+    let column = before.get(line_start..).unwrap_or_default().chars().count() + 1;
+    (
+        line,
+        column,
+        input.get(line_start..line_end).unwrap_or_default(),
+    )
+}
+
+/// Offset of `rest` in `input`, `rest` should be a subslice of it (as all the nom leftovers are)
+fn offset_of(input: &str, rest: &str) -> usize {
+    #![allow(clippy::as_conversions)]
+    let start = input.as_ptr() as usize;
+    let pos = rest.as_ptr() as usize;
+    if (start..=start + input.len()).contains(&pos) {
+        pos - start
+    } else {
+        input.len().saturating_sub(rest.len())
+    }
+}
+
+/// `'x'` or `end of input`
+fn found(rest: &str) -> String {
+    rest.chars()
+        .next()
+        .map_or_else(|| "end of input".to_owned(), |c| format!("{c:?}"))
+}
+
+fn kind_message(kind: ErrorKind, rest: &str) -> String {
+    format!("{} failed at {}", kind.description(), found(rest))
+}
+
+impl Diagnostic {
+    /// Error at the start of `rest`, a part of `input`
+    pub fn new(input: &str, rest: &str, message: impl Into<String>) -> Self {
+        let (line, column, snippet) = locate(input, offset_of(input, rest));
+        Self {
+            message: message.into(),
+            file: None,
+            line,
+            column,
+            snippet: snippet.to_owned(),
+            notes: Vec::new(),
+        }
+    }
+
+    /// Parser didn't consume `rest`
+    pub fn unparsed(input: &str, rest: &str) -> Self {
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        let (bytes, lines) = (rest.len(), rest.lines().count());
+        let message = format!(
+            "parsing stopped here, {bytes} byte{} ({lines} line{}) left unparsed",
+            plural(bytes),
+            plural(lines)
+        );
+        Self::new(input, rest, message)
+    }
+
+    pub fn from_error(input: &str, e: &Error<&str>) -> Self {
+        Self::new(input, e.input, kind_message(e.code, e.input))
+    }
+
+    /// Innermost error is the main one, contexts become notes
+    pub fn from_verbose(input: &str, e: &VerboseError<&str>) -> Self {
+        let Some((rest, kind)) = e.errors.first() else {
+            return Self::new(input, input, "unknown error");
+        };
+        let message = match kind {
+            VerboseErrorKind::Char(c) => format!("expected {c:?}, found {}", found(rest)),
+            VerboseErrorKind::Context(ctx) => format!("invalid {ctx} at {}", found(rest)),
+            VerboseErrorKind::Nom(kind) => kind_message(*kind, rest),
+        };
+        let mut res = Self::new(input, rest, message);
+        res.notes = e
+            .errors
+            .iter()
+            .skip(1)
+            .filter_map(|(rest, kind)| match kind {
+                VerboseErrorKind::Context(ctx) => {
+                    let (line, column, _) = locate(input, offset_of(input, rest));
+                    Some(format!("while parsing {ctx} at {line}:{column}"))
+                }
+                VerboseErrorKind::Char(_) | VerboseErrorKind::Nom(_) => None,
+            })
+            .collect();
+        res
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Sets the file name if the error is a diagnostic, other errors are left as is
+    pub fn set_file(e: &mut anyhow::Error, file: impl Into<String>) {
+        if let Some(d) = e.downcast_mut::<Self>() {
+            d.file = Some(file.into());
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = self.file.as_deref().unwrap_or("input");
+        let gutter = self.line.to_string().len();
+        let pad = "";
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{pad:gutter$}--> {file}:{}:{}", self.line, self.column)?;
+        writeln!(f, "{pad:gutter$} |")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{pad:gutter$} | {pad:caret$}^", caret = self.column - 1)?;
+        for note in &self.notes {
+            write!(f, "\n{pad:gutter$} = {note}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{bytes::complete::tag, character::complete::char as nchar, error::context};
+
+    #[test]
+    fn test_locate() {
+        let input = "abc\ndéf\n";
+        assert_eq!(locate(input, 0), (1, 1, "abc"));
+        assert_eq!(locate(input, 3), (1, 4, "abc"));
+        assert_eq!(locate(input, 7), (2, 3, "déf"));
+        assert_eq!(locate(input, input.len()), (3, 1, ""));
+    }
+
+    #[test]
+    fn test_render() {
+        let input = "move 1\nmvoe 2\n";
+        let rest = input.get(7..).unwrap();
+        let e = context("move", tag::<_, _, VerboseError<&str>>("move"))(rest).unwrap_err();
+        let nom::Err::Error(e) = e else { panic!() };
+        let mut d = Diagnostic::from_verbose(input, &e);
+        d.file = Some("test.txt".to_owned());
+        assert_eq!(
+            d.to_string(),
+            "error: Tag failed at 'm'\n --> test.txt:2:1\n  |\n2 | mvoe 2\n  | ^\n  = while parsing move at 2:1"
+        );
+
+        let rest = input.get(9..).unwrap();
+        let e = nchar::<_, VerboseError<&str>>('x')(rest).unwrap_err();
+        let nom::Err::Error(e) = e else { panic!() };
+        let d = Diagnostic::from_verbose(input, &e);
+        assert_eq!(d.message, "expected 'x', found 'o'");
+        assert_eq!((d.line, d.column), (2, 3));
+        assert!(d.to_string().ends_with("2 | mvoe 2\n  |   ^"));
+    }
+
+    #[test]
+    fn test_set_file() {
+        let mut e = anyhow::Error::new(Diagnostic::new("a", "a", "bad")).context("Failed");
+        Diagnostic::set_file(&mut e, "input.txt");
+        assert!(format!("{e:#}").contains("--> input.txt:1:1"));
+    }
+}
@@ -22,6 +22,11 @@ where
             None => None,
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
 
 pub struct FilterMapOkRes<I, TI, TO, E, F>
@@ -54,6 +59,323 @@ where
             };
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+pub struct AndThenOk<I, TI, TO, E, EF, F>
+where
+    I: Iterator<Item = Result<TI, E>>,
+    F: FnMut(TI) -> Result<TO, EF>,
+    EF: Into<E>,
+{
+    iter: I,
+    f: F,
+}
+
+impl<I, TI, TO, E, EF, F> Iterator for AndThenOk<I, TI, TO, E, EF, F>
+where
+    I: Iterator<Item = Result<TI, E>>,
+    F: FnMut(TI) -> Result<TO, EF>,
+    EF: Into<E>,
+{
+    type Item = Result<TO, E>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(
+            self.iter
+                .next()?
+                .and_then(|v| (self.f)(v).map_err(Into::into)),
+        )
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct FlatMapOkRes<I, TI, E, J, F>
+where
+    I: Iterator<Item = Result<TI, E>>,
+    J: IntoIterator,
+    F: FnMut(TI) -> Result<J, E>,
+{
+    iter: I,
+    f: F,
+    cur: Option<J::IntoIter>,
+}
+
+impl<I, TI, E, J, F> Iterator for FlatMapOkRes<I, TI, E, J, F>
+where
+    I: Iterator<Item = Result<TI, E>>,
+    J: IntoIterator,
+    F: FnMut(TI) -> Result<J, E>,
+{
+    type Item = Result<J::Item, E>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(v) = self.cur.as_mut().and_then(Iterator::next) {
+                return Some(Ok(v));
+            }
+            self.cur = None;
+            match self.iter.next()?.and_then(&mut self.f) {
+                Ok(cur) => self.cur = Some(cur.into_iter()),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (cur_low, cur_high) = self.cur.as_ref().map_or((0, Some(0)), Iterator::size_hint);
+        match self.iter.size_hint() {
+            (_, Some(0)) => (cur_low, cur_high),
+            _ => (cur_low, None),
+        }
+    }
+}
+
+pub struct ScanOk<I, T, E, St, TO, F>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(&mut St, T) -> Option<TO>,
+{
+    iter: I,
+    state: St,
+    f: F,
+}
+
+impl<I, T, E, St, TO, F> Iterator for ScanOk<I, T, E, St, TO, F>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(&mut St, T) -> Option<TO>,
+{
+    type Item = Result<TO, E>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next()? {
+            Ok(v) => (self.f)(&mut self.state, v).map(Ok),
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+pub struct TakeWhileOk<I, T, E, P>
+where
+    I: Iterator<Item = Result<T, E>>,
+    P: FnMut(&T) -> bool,
+{
+    iter: I,
+    pred: P,
+    done: bool,
+}
+
+impl<I, T, E, P> Iterator for TakeWhileOk<I, T, E, P>
+where
+    I: Iterator<Item = Result<T, E>>,
+    P: FnMut(&T) -> bool,
+{
+    type Item = Result<T, E>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.iter.next()? {
+            Ok(v) if !(self.pred)(&v) => {
+                self.done = true;
+                None
+            }
+            res => Some(res),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+pub struct TryChunks<I, T, E>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    iter: I,
+    size: usize,
+}
+
+impl<I, T, E> Iterator for TryChunks<I, T, E>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    type Item = Result<Vec<T>, E>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(self.size);
+        while chunk.len() < self.size {
+            match self.iter.next() {
+                Some(Ok(v)) => chunk.push(v),
+                Some(Err(e)) => return Some(Err(e)),
+                None => break,
+            }
+        }
+        (!chunk.is_empty()).then_some(Ok(chunk))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every error is an item of its own
+        let (low, high) = self.iter.size_hint();
+        (low.div_ceil(self.size), high)
+    }
+}
+
+pub struct InspectErr<I, T, E, F>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(&E),
+{
+    iter: I,
+    f: F,
+}
+
+impl<I, T, E, F> Iterator for InspectErr<I, T, E, F>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(&E),
+{
+    type Item = Result<T, E>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.iter.next()?.inspect_err(&mut self.f))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// See [`ExtraItertools::enumerate_errors`] and [`ExtraItertools::with_position_context`]
+pub struct PositionContext<'source, I> {
+    iter: I,
+    index: usize,
+    source: Option<std::str::Lines<'source>>,
+}
+
+impl<I, T> Iterator for PositionContext<'_, I>
+where
+    I: Iterator<Item = anyhow::Result<T>>,
+{
+    type Item = anyhow::Result<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        self.index += 1;
+        let number = self.index;
+        let line = self
+            .source
+            .as_mut()
+            .map(|lines| lines.next().unwrap_or_default());
+        Some(match line {
+            Some(line) => crate::lines::at_line((number, line.trim()), item),
+            None => anyhow::Context::with_context(item, || format!("Element {number}")),
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// See [`ExtraItertools::split_by`]
+pub struct SplitBy<I, T, E, P>
+where
+    I: Iterator<Item = Result<T, E>>,
+    P: FnMut(&T) -> bool,
+{
+    iter: I,
+    is_separator: P,
+}
+
+impl<I, T, E, P> Iterator for SplitBy<I, T, E, P>
+where
+    I: Iterator<Item = Result<T, E>>,
+    P: FnMut(&T) -> bool,
+{
+    type Item = Result<Vec<T>, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut group = Vec::new();
+        loop {
+            match self.iter.next() {
+                Some(Ok(v)) if (self.is_separator)(&v) => {
+                    if !group.is_empty() {
+                        return Some(Ok(group));
+                    }
+                }
+                Some(Ok(v)) => group.push(v),
+                Some(Err(e)) => return Some(Err(e)),
+                None => return (!group.is_empty()).then_some(Ok(group)),
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// Keeps the `K` items with the largest keys, sorted from the largest.
+/// Items with equal keys keep their order.
+struct TopK<T, B, const K: usize>(Vec<(B, T)>);
+
+impl<T, B: Ord, const K: usize> TopK<T, B, K> {
+    fn new() -> Self {
+        Self(Vec::with_capacity(K + 1))
+    }
+
+    fn push(&mut self, key: B, item: T) {
+        if K == 0 || matches!(self.0.last(), Some((min, _)) if self.0.len() == K && key <= *min) {
+            return;
+        }
+        let pos = self.0.partition_point(|(b, _)| *b >= key);
+        self.0.insert(pos, (key, item));
+        self.0.truncate(K);
+    }
+
+    fn finish(self) -> anyhow::Result<[T; K]> {
+        let found = self.0.len();
+        self.0
+            .into_iter()
+            .map(|(_, item)| item)
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Only {found} item(s), expected at least {K}"))
+    }
 }
 
 pub trait ExtraItertools: Iterator {
@@ -74,6 +396,384 @@ pub trait ExtraItertools: Iterator {
     {
         FilterMapOkRes { iter: self, f }
     }
+
+    /// Like `map_ok_res`, but the error of `f` only needs to convert into the error of the items
+    #[inline]
+    fn and_then_ok<F, TI, TO, E, EF>(self, f: F) -> AndThenOk<Self, TI, TO, E, EF, F>
+    where
+        F: FnMut(TI) -> Result<TO, EF>,
+        EF: Into<E>,
+        Self: Iterator<Item = Result<TI, E>> + Sized,
+    {
+        AndThenOk { iter: self, f }
+    }
+
+    /// Flattens the iterables returned by `f` for the `Ok` items
+    #[inline]
+    fn flat_map_ok_res<F, TI, E, J>(self, f: F) -> FlatMapOkRes<Self, TI, E, J, F>
+    where
+        J: IntoIterator,
+        F: FnMut(TI) -> Result<J, E>,
+        Self: Iterator<Item = Result<TI, E>> + Sized,
+    {
+        FlatMapOkRes {
+            iter: self,
+            f,
+            cur: None,
+        }
+    }
+
+    /// `scan` over the `Ok` items, errors are passed through
+    #[inline]
+    fn scan_ok<St, F, T, TO, E>(self, state: St, f: F) -> ScanOk<Self, T, E, St, TO, F>
+    where
+        F: FnMut(&mut St, T) -> Option<TO>,
+        Self: Iterator<Item = Result<T, E>> + Sized,
+    {
+        ScanOk {
+            iter: self,
+            state,
+            f,
+        }
+    }
+
+    /// Stops at the first `Ok` item that doesn't match, errors are passed through
+    #[inline]
+    fn take_while_ok<P, T, E>(self, pred: P) -> TakeWhileOk<Self, T, E, P>
+    where
+        P: FnMut(&T) -> bool,
+        Self: Iterator<Item = Result<T, E>> + Sized,
+    {
+        TakeWhileOk {
+            iter: self,
+            pred,
+            done: false,
+        }
+    }
+
+    /// `Ok` items by `size`, the last chunk may be shorter.
+    /// Errors are passed through, the chunk they interrupted is lost.
+    ///
+    /// # Panics
+    /// If `size` is 0
+    #[inline]
+    fn try_chunks<T, E>(self, size: usize) -> TryChunks<Self, T, E>
+    where
+        Self: Iterator<Item = Result<T, E>> + Sized,
+    {
+        assert!(size != 0, "Chunk size must be positive");
+        TryChunks { iter: self, size }
+    }
+
+    #[inline]
+    fn inspect_err<F, T, E>(self, f: F) -> InspectErr<Self, T, E, F>
+    where
+        F: FnMut(&E),
+        Self: Iterator<Item = Result<T, E>> + Sized,
+    {
+        InspectErr { iter: self, f }
+    }
+
+    /// Collects all the `Ok` values and all the errors, instead of stopping at the first error
+    #[inline]
+    fn collect_partitioned<C, T, E>(self) -> (C, Vec<E>)
+    where
+        C: Default + Extend<T>,
+        Self: Iterator<Item = Result<T, E>> + Sized,
+    {
+        let mut ok = C::default();
+        let mut errors = Vec::new();
+        for item in self {
+            match item {
+                Ok(v) => ok.extend(Some(v)),
+                Err(e) => errors.push(e),
+            }
+        }
+        (ok, errors)
+    }
+
+    /// Adds the 1-based number of the element to the errors, like the line numbers
+    #[inline]
+    fn enumerate_errors<T>(self) -> PositionContext<'static, Self>
+    where
+        Self: Iterator<Item = anyhow::Result<T>> + Sized,
+    {
+        PositionContext {
+            iter: self,
+            index: 0,
+            source: None,
+        }
+    }
+
+    /// Adds the line number and the line of `source` to the errors,
+    /// the elements must correspond to the lines of `source` one to one
+    #[inline]
+    fn with_position_context<T>(self, source: &str) -> PositionContext<'_, Self>
+    where
+        Self: Iterator<Item = anyhow::Result<T>> + Sized,
+    {
+        PositionContext {
+            iter: self,
+            index: 0,
+            source: Some(source.lines()),
+        }
+    }
+
+    /// Groups of `Ok` items between the separators. Separators are dropped and never produce
+    /// empty groups, no matter if they are leading, trailing or repeated.
+    /// Errors are passed through, the group they interrupted is lost.
+    #[inline]
+    fn split_by<P, T, E>(self, is_separator: P) -> SplitBy<Self, T, E, P>
+    where
+        P: FnMut(&T) -> bool,
+        Self: Iterator<Item = Result<T, E>> + Sized,
+    {
+        SplitBy {
+            iter: self,
+            is_separator,
+        }
+    }
+
+    /// `K` largest items from the largest one, fails if there are less than `K`
+    #[inline]
+    fn top_k<const K: usize>(self) -> anyhow::Result<[Self::Item; K]>
+    where
+        Self::Item: Ord + Clone,
+        Self: Sized,
+    {
+        self.top_k_by_key(Clone::clone)
+    }
+
+    /// Items with the `K` largest keys, see [`ExtraItertools::top_k`]
+    #[inline]
+    fn top_k_by_key<const K: usize, B, F>(self, mut f: F) -> anyhow::Result<[Self::Item; K]>
+    where
+        B: Ord,
+        F: FnMut(&Self::Item) -> B,
+        Self: Sized,
+    {
+        let mut top = TopK::<_, _, K>::new();
+        for item in self {
+            top.push(f(&item), item);
+        }
+        top.finish()
+    }
+
+    /// [`ExtraItertools::top_k`] of the `Ok` items, stops at the first error
+    #[inline]
+    fn top_k_ok<const K: usize, T, E>(self) -> anyhow::Result<[T; K]>
+    where
+        T: Ord + Clone,
+        E: Into<anyhow::Error>,
+        Self: Iterator<Item = Result<T, E>> + Sized,
+    {
+        let mut top = TopK::<_, _, K>::new();
+        for item in self {
+            let item = item.map_err(Into::into)?;
+            top.push(item.clone(), item);
+        }
+        top.finish()
+    }
+
+    /// Lines grouped by blank (whitespace only) lines, see [`ExtraItertools::split_by`]
+    #[inline]
+    fn groups_by_blank_lines<S, E>(self) -> SplitBy<Self, S, E, fn(&S) -> bool>
+    where
+        S: AsRef<str>,
+        Self: Iterator<Item = Result<S, E>> + Sized,
+    {
+        self.split_by(|line| line.as_ref().trim().is_empty())
+    }
 }
 
 impl<I: Iterator + ?Sized> ExtraItertools for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEMS: [Result<u32, &str>; 5] = [Ok(1), Ok(2), Err("bad"), Ok(3), Ok(4)];
+
+    #[test]
+    fn test_map_ok() {
+        let mapped = ITEMS.into_iter().map_ok_res(|v| Ok(v * 2));
+        assert_eq!(mapped.size_hint(), (5, Some(5)));
+        let filtered = ITEMS
+            .into_iter()
+            .filter_map_ok_res(|v| Ok((v % 2 == 0).then_some(v)));
+        assert_eq!(filtered.size_hint(), (0, Some(5)));
+        assert_eq!(filtered.collect::<Vec<_>>(), [Ok(2), Err("bad"), Ok(4)]);
+    }
+
+    #[test]
+    fn test_and_then_ok() {
+        #[derive(Debug, PartialEq)]
+        struct Error(String);
+        impl From<&str> for Error {
+            fn from(e: &str) -> Self {
+                Self(e.to_owned())
+            }
+        }
+
+        let items = ITEMS.map(|item| item.map_err(Error::from));
+        let res = items
+            .into_iter()
+            .and_then_ok(|v| if v == 4 { Err("four") } else { Ok(v) });
+        assert_eq!(res.size_hint(), (5, Some(5)));
+        assert_eq!(
+            res.collect::<Vec<_>>(),
+            [
+                Ok(1),
+                Ok(2),
+                Err(Error::from("bad")),
+                Ok(3),
+                Err(Error::from("four"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flat_map_ok_res() {
+        let mut res =
+            ITEMS
+                .into_iter()
+                .flat_map_ok_res(|v| if v == 3 { Err("three") } else { Ok(vec![v; 2]) });
+        assert_eq!(res.size_hint(), (0, None));
+        assert_eq!(res.next(), Some(Ok(1)));
+        assert_eq!(res.size_hint(), (1, None));
+        assert_eq!(
+            res.collect::<Vec<_>>(),
+            [Ok(1), Ok(2), Ok(2), Err("bad"), Err("three"), Ok(4), Ok(4)]
+        );
+        let mut last = [Ok::<_, ()>(0)].into_iter().flat_map_ok_res(|_| Ok([1, 2]));
+        last.next();
+        assert_eq!(last.size_hint(), (1, Some(1)));
+    }
+
+    #[test]
+    fn test_scan_ok() {
+        let res = ITEMS.into_iter().scan_ok(0, |sum, v| {
+            *sum += v;
+            (*sum < 6).then_some(*sum)
+        });
+        assert_eq!(res.size_hint(), (0, Some(5)));
+        assert_eq!(res.collect::<Vec<_>>(), [Ok(1), Ok(3), Err("bad")]);
+    }
+
+    #[test]
+    fn test_take_while_ok() {
+        let mut res = ITEMS.into_iter().take_while_ok(|&v| v < 3);
+        assert_eq!(res.size_hint(), (0, Some(5)));
+        assert_eq!(res.by_ref().collect::<Vec<_>>(), [Ok(1), Ok(2), Err("bad")]);
+        assert_eq!(res.size_hint(), (0, Some(0)));
+        assert_eq!(res.next(), None);
+    }
+
+    #[test]
+    fn test_try_chunks() {
+        let res = ITEMS.into_iter().try_chunks(2);
+        assert_eq!(res.size_hint(), (3, Some(5)));
+        assert_eq!(
+            res.collect::<Vec<_>>(),
+            [Ok(vec![1, 2]), Err("bad"), Ok(vec![3, 4])]
+        );
+        let odd: Vec<_> = [Ok::<_, ()>(1), Ok(2), Ok(3)]
+            .into_iter()
+            .try_chunks(2)
+            .collect();
+        assert_eq!(odd, [Ok(vec![1, 2]), Ok(vec![3])]);
+    }
+
+    #[test]
+    fn test_inspect_err() {
+        let mut errors = Vec::new();
+        let res = ITEMS.into_iter().inspect_err(|e| errors.push(*e));
+        assert_eq!(res.size_hint(), (5, Some(5)));
+        assert_eq!(res.filter_map(Result::ok).sum::<u32>(), 10);
+        assert_eq!(errors, ["bad"]);
+    }
+
+    #[test]
+    fn test_collect_partitioned() {
+        let items = [Ok(1), Err("a"), Ok(2), Err("b")];
+        let (ok, errors): (Vec<_>, _) = items.into_iter().collect_partitioned();
+        assert_eq!(ok, [1, 2]);
+        assert_eq!(errors, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_position_context() {
+        let parse = |s: &str| s.parse::<u32>().map_err(anyhow::Error::from);
+        let errors = |res: Vec<anyhow::Result<u32>>| {
+            res.into_iter()
+                .filter_map(|res| res.err().map(|e| format!("{e:#}")))
+                .collect::<Vec<_>>()
+        };
+
+        let input = "1\nx\n3\n-4";
+        let res: Vec<_> = input.split('\n').map(parse).enumerate_errors().collect();
+        assert_eq!(res.len(), 4);
+        let errors_by_index = errors(res);
+        assert!(errors_by_index[0].starts_with("Element 2: invalid digit"));
+        assert!(errors_by_index[1].starts_with("Element 4: "));
+
+        let res = input
+            .lines()
+            .map(parse)
+            .with_position_context(input)
+            .collect();
+        let errors_by_line = errors(res);
+        assert!(errors_by_line[0].starts_with("Line 2: 'x': invalid digit"));
+        assert!(errors_by_line[1].starts_with("Line 4: '-4': "));
+    }
+
+    #[test]
+    fn test_groups_by_blank_lines() {
+        fn groups(s: &str) -> Vec<Vec<&str>> {
+            s.split('\n')
+                .map(Ok::<_, ()>)
+                .groups_by_blank_lines()
+                .collect::<Result<_, _>>()
+                .unwrap()
+        }
+        assert_eq!(groups("a\nb\n\nc"), [vec!["a", "b"], vec!["c"]]);
+        assert_eq!(groups("\n \na\n\n\n\nb\n\n"), [vec!["a"], vec!["b"]]);
+        assert!(groups("").is_empty());
+        assert!(groups("\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_top_k() {
+        let items = [3, 1, 4, 1, 5, 9, 2, 6];
+        assert_eq!(items.into_iter().top_k::<3>().unwrap(), [9, 6, 5]);
+        assert_eq!(items.into_iter().top_k::<0>().unwrap(), [0_i32; 0]);
+        let err = items.into_iter().top_k::<9>().unwrap_err().to_string();
+        assert_eq!(err, "Only 8 item(s), expected at least 9");
+
+        let words = ["bb", "a", "cc", "ddd"];
+        assert_eq!(
+            words
+                .into_iter()
+                .top_k_by_key::<3, _, _>(|w| w.len())
+                .unwrap(),
+            ["ddd", "bb", "cc"]
+        );
+
+        let ok: [Result<u32, anyhow::Error>; 3] = [Ok(1), Ok(3), Ok(2)];
+        assert_eq!(ok.into_iter().top_k_ok::<2, _, _>().unwrap(), [3, 2]);
+        let failed = [Ok(1), Err(anyhow::anyhow!("bad")), Ok(2)];
+        let err = failed.into_iter().top_k_ok::<1, _, _>().unwrap_err();
+        assert_eq!(err.to_string(), "bad");
+    }
+
+    #[test]
+    fn test_split_by() {
+        let items = [Ok(1), Ok(0), Ok(2), Err("bad"), Ok(3), Ok(0), Ok(4)];
+        let groups: Vec<_> = items.into_iter().split_by(|&v| v == 0).collect();
+        assert_eq!(groups, [Ok(vec![1]), Err("bad"), Ok(vec![3]), Ok(vec![4])]);
+        assert_eq!(
+            items.into_iter().split_by(|&v| v == 0).size_hint(),
+            (0, Some(7))
+        );
+    }
+}
@@ -0,0 +1,388 @@
+use anyhow::{bail, Context, Result};
+use std::{
+    fmt::{self, Display},
+    iter,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// `(x, y)`, `(0, 0)` is the top left corner
+pub type Pos = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Dir {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Self::Up => (0, -1),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+            Self::Right => (1, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// Positions from `start` (included) to the edge of a `width`x`height` grid
+fn walk(start: Pos, dir: Dir, width: usize, height: usize) -> impl Iterator<Item = Pos> {
+    let (dx, dy) = dir.offset();
+    iter::successors(Some(start), move |&(x, y)| {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        (pos.0 < width && pos.1 < height).then_some(pos)
+    })
+}
+
+/// Rectangular grid stored row by row
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self> {
+        if width.checked_mul(height) != Some(cells.len()) {
+            bail!("{} cells don't make a {width}x{height} grid", cells.len());
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn from_elem(width: usize, height: usize, elem: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![elem; width * height],
+        }
+    }
+
+    /// Parses a block of characters, one row per line. All rows must have the same length.
+    /// Only the empty lines around the block are skipped, spaces can be cells.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+        for (y, line) in input.trim_matches(['\n', '\r']).lines().enumerate() {
+            let len = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).with_context(|| {
+                    format!("Bad cell '{c}' at line {}, column {}", y + 1, x + 1)
+                })?);
+            }
+            let row_width = cells.len() - len;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => bail!(
+                    "Line {} has {row_width} cells, expected {width} like the first one",
+                    y + 1
+                ),
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Self::new(width.unwrap_or(0), height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos).then(|| pos.1 * self.width + pos.0)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).and_then(|i| self.cells.get(i))
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).and_then(|i| self.cells.get_mut(i))
+    }
+
+    /// Position moved by the offset, if it's still inside the grid
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    pub fn step(&self, pos: Pos, dir: Dir) -> Option<Pos> {
+        self.offset(pos, dir.offset())
+    }
+
+    /// All positions, row by row
+    pub fn positions(&self) -> impl DoubleEndedIterator<Item = Pos> {
+        let (width, height) = (self.width, self.height);
+        (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        self.positions().zip(&mut self.cells)
+    }
+
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn into_values(self) -> std::vec::IntoIter<T> {
+        self.cells.into_iter()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        let start = y.checked_mul(self.width)?;
+        (y < self.height).then(|| self.cells.get(start..start + self.width))?
+    }
+
+    /// Rows from top to bottom, `.rev()` goes from the bottom, and so does `.rev()` of a row
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    /// Column from top to bottom, `.rev()` goes from the bottom
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).filter_map(move |y| self.get((x, y)))
+    }
+
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Positions from `from` (not included) to the edge of the grid in the direction
+    pub fn ray_positions(&self, from: Pos, dir: Dir) -> impl Iterator<Item = Pos> {
+        walk(from, dir, self.width, self.height).skip(1)
+    }
+
+    /// Cells from `from` (not included) to the edge of the grid in the direction
+    pub fn ray(&self, from: Pos, dir: Dir) -> impl Iterator<Item = (Pos, &T)> {
+        self.ray_positions(from, dir)
+            .filter_map(|pos| Some((pos, self.get(pos)?)))
+    }
+
+    /// Every row or column as positions going in the direction, starting from the opposite edge.
+    /// Doesn't borrow the grid, so the cells can be modified along the way.
+    pub fn lines(&self, dir: Dir) -> impl Iterator<Item = impl Iterator<Item = Pos>> {
+        let (width, height) = (self.width, self.height);
+        let starts: Box<dyn Iterator<Item = Pos>> = match dir {
+            _ if width == 0 || height == 0 => Box::new(iter::empty()),
+            Dir::Right => Box::new((0..height).map(|y| (0, y))),
+            Dir::Left => Box::new((0..height).map(move |y| (width - 1, y))),
+            Dir::Down => Box::new((0..width).map(|x| (x, 0))),
+            Dir::Up => Box::new((0..width).map(move |x| (x, height - 1))),
+        };
+        starts.map(move |start| walk(start, dir, width, height))
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbours(pos, &NEIGHBOURS4)
+    }
+
+    /// Including the diagonal ones
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbours(pos, &NEIGHBOURS8)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        pos: Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (Pos, &'a T)> {
+        offsets.iter().filter_map(move |&offset| {
+            let pos = self.offset(pos, offset)?;
+            Some((pos, self.get(pos)?))
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        let (width, height) = (self.width, self.height);
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the {width}x{height} grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the {width}x{height} grid"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Ok)
+    }
+}
+
+/// Single character cells are printed as a block, wider ones are right-aligned in columns
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<String> = self.cells.iter().map(ToString::to_string).collect();
+        let width = cells.iter().map(|c| c.chars().count()).max().unwrap_or(0);
+        for row in cells.chunks(self.width.max(1)) {
+            for (x, cell) in row.iter().enumerate() {
+                if width > 1 && x != 0 {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{cell:>width$}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let g = grid();
+        assert_eq!((g.width(), g.height(), g.len()), (3, 2, 6));
+        assert_eq!(g.get((2, 0)), Some(&'c'));
+        assert_eq!(g.get((0, 1)), Some(&'d'));
+        assert_eq!(g.get((3, 0)), None);
+        assert_eq!(g.get((0, 2)), None);
+        assert_eq!(g[(1, 1)], 'e');
+
+        let err = "abc\nde".parse::<Grid<char>>().unwrap_err();
+        assert!(err.to_string().contains("Line 2"), "{err}");
+        let err = Grid::parse("12\n3x", |c| c.to_digit(10).context("Not a digit")).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("'x' at line 2, column 2"),
+            "{err:#}"
+        );
+        assert!("".parse::<Grid<char>>().unwrap().is_empty());
+
+        let g: Grid<char> = "\n #\n##\r\n# \n\n".parse().unwrap();
+        assert_eq!((g.width(), g.height()), (2, 3));
+        assert_eq!((g[(0, 0)], g[(1, 2)]), (' ', ' '));
+        assert!(Grid::new(2, 2, vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let g = grid();
+        assert_eq!(g.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(g.row(2), None);
+        assert_eq!(g.rows().rev().count(), 2);
+        assert_eq!(g.column(1).collect::<String>(), "be");
+        assert_eq!(g.column(2).rev().collect::<String>(), "fc");
+        assert_eq!(g.column(3).count(), 0);
+        let columns: Vec<String> = g.columns().map(Iterator::collect).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn test_rays_and_lines() {
+        let g = grid();
+        let ray = |pos, dir| g.ray(pos, dir).map(|(_, c)| c).collect::<String>();
+        assert_eq!(ray((0, 0), Dir::Right), "bc");
+        assert_eq!(ray((2, 1), Dir::Left), "ed");
+        assert_eq!(ray((1, 1), Dir::Up), "b");
+        assert_eq!(ray((1, 0), Dir::Down), "e");
+        assert_eq!(ray((2, 0), Dir::Right), "");
+
+        let lines = |dir| {
+            g.lines(dir)
+                .map(|line| line.map(|pos| g[pos]).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lines(Dir::Right), ["abc", "def"]);
+        assert_eq!(lines(Dir::Left), ["cba", "fed"]);
+        assert_eq!(lines(Dir::Down), ["ad", "be", "cf"]);
+        assert_eq!(lines(Dir::Up), ["da", "eb", "fc"]);
+        assert_eq!(Grid::<char>::default().lines(Dir::Up).count(), 0);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let g = grid();
+        let n4: String = g.neighbours4((0, 0)).map(|(_, c)| c).collect();
+        assert_eq!(n4, "bd");
+        let n8: String = g.neighbours8((1, 0)).map(|(_, c)| c).collect();
+        assert_eq!(n8, "cfeda");
+        assert_eq!(g.neighbours8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(grid().to_string(), "abc\ndef\n");
+        let numbers = Grid::new(2, 2, vec![1, 20, 300, 4]).unwrap();
+        assert_eq!(numbers.to_string(), "  1  20\n300   4\n");
+        assert_eq!(grid().map(|c| c.is_ascii_digit()).len(), 6);
+    }
+}
@@ -0,0 +1,276 @@
+use anyhow::{bail, Result};
+use std::fmt::{self, Debug, Display};
+
+/// Integer that can be an end of an [`Interval`]
+pub trait Point: Copy + Ord + Debug + Display {
+    /// Next integer, `None` at the max value
+    fn succ(self) -> Option<Self>;
+    /// Number of integers in `[start, end]`
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_point {
+    ($($t:ty),*) => {$(
+        impl Point for $t {
+            #[inline]
+            fn succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            #[inline]
+            fn count(start: Self, end: Self) -> u128 {
+                // all the implementors fit into i128
+                #![allow(clippy::as_conversions, clippy::cast_sign_loss, clippy::cast_lossless)]
+                (end as i128 - start as i128 + 1) as u128
+            }
+        }
+    )*};
+}
+
+impl_point!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Closed interval `[start, end]`, never empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Point> Interval<T> {
+    pub fn new(start: T, end: T) -> Result<Self> {
+        if start > end {
+            bail!("Interval {start}-{end} ends before it starts");
+        }
+        Ok(Self { start, end })
+    }
+
+    pub fn point(p: T) -> Self {
+        Self { start: p, end: p }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// Number of integers in the interval
+    #[allow(clippy::len_without_is_empty)] // intervals are never empty
+    pub fn len(&self) -> u128 {
+        T::count(self.start, self.end)
+    }
+
+    pub fn contains(&self, p: T) -> bool {
+        self.start <= p && p <= self.end
+    }
+
+    /// `other` is completely inside of `self`
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Overlapping or next to each other, so that the union is an interval
+    fn touches(&self, other: &Self) -> bool {
+        let adjacent = |a: &Self, b: &Self| a.end.succ() == Some(b.start);
+        self.overlaps(other) || adjacent(self, other) || adjacent(other, self)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other).then(|| Self {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        })
+    }
+
+    /// `None` if there's a gap between the intervals
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.touches(other).then(|| Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Union of intervals, stored as sorted disjoint intervals with gaps between them
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T>(Vec<Interval<T>>);
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T: Point> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merges the interval with all the ones it touches
+    pub fn insert(&mut self, iv: Interval<T>) {
+        // Intervals before `from` end before `iv` (with a gap), the ones from `to` start after it
+        let from = self
+            .0
+            .partition_point(|cur| cur.end < iv.start && !cur.touches(&iv));
+        let to = self
+            .0
+            .partition_point(|cur| cur.start <= iv.end || cur.touches(&iv));
+        let merged = self.0.drain(from..to).fold(iv, |merged, cur| Interval {
+            start: merged.start.min(cur.start),
+            end: merged.end.max(cur.end),
+        });
+        self.0.insert(from, merged);
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of integers in the set
+    pub fn len(&self) -> u128 {
+        self.0.iter().map(Interval::len).sum()
+    }
+
+    /// Interval of the set that contains the point
+    fn find(&self, p: T) -> Option<&Interval<T>> {
+        let i = self.0.partition_point(|cur| cur.end < p);
+        self.0.get(i).filter(|cur| cur.contains(p))
+    }
+
+    pub fn contains(&self, p: T) -> bool {
+        self.find(p).is_some()
+    }
+
+    /// Every point of the interval is in the set
+    pub fn covers(&self, iv: &Interval<T>) -> bool {
+        self.find(iv.start)
+            .is_some_and(|cur| cur.contains_interval(iv))
+    }
+
+    /// Number of integers of the interval that are in the set
+    pub fn coverage(&self, iv: &Interval<T>) -> u128 {
+        let from = self.0.partition_point(|cur| cur.end < iv.start);
+        self.0
+            .get(from..)
+            .unwrap_or_default()
+            .iter()
+            .map_while(|cur| cur.intersection(iv))
+            .map(|common| common.len())
+            .sum()
+    }
+}
+
+impl<T: Point> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Point> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for iv in iter {
+            self.insert(iv);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn iv(start: i8, end: i8) -> Interval<i8> {
+        Interval::new(start, end).unwrap()
+    }
+
+    fn points(iv: &Interval<i8>) -> BTreeSet<i8> {
+        (iv.start()..=iv.end()).collect()
+    }
+
+    fn interval() -> impl Strategy<Value = Interval<i8>> {
+        (-20_i8..20, 0_i8..10).prop_map(|(start, len)| iv(start, start + len))
+    }
+
+    #[test]
+    fn test_interval() {
+        assert!(Interval::new(3, 2).is_err());
+        assert_eq!(iv(-2, 3).len(), 6);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).unwrap().len(), 1 << 64);
+        assert_eq!(iv(1, 2).union(&iv(3, 4)), Some(iv(1, 4)));
+        assert_eq!(iv(1, 2).union(&iv(4, 5)), None);
+        assert_eq!(iv(1, 5).intersection(&iv(4, 8)), Some(iv(4, 5)));
+        assert_eq!(iv(i8::MAX, i8::MAX).union(&iv(0, 1)), None);
+        assert_eq!(iv(2, 4).to_string(), "2-4");
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let set: IntervalSet<i8> = [iv(10, 12), iv(1, 2), iv(5, 6), iv(3, 3), iv(11, 20)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.intervals(), [iv(1, 3), iv(5, 6), iv(10, 20)]);
+        assert_eq!(set.len(), 3 + 2 + 11);
+        assert!(set.covers(&iv(12, 18)));
+        assert!(!set.covers(&iv(3, 5)));
+        assert_eq!(set.coverage(&iv(3, 11)), 1 + 2 + 2);
+    }
+
+    proptest! {
+        #[test]
+        fn interval_matches_model(a in interval(), b in interval(), p in -25_i8..35) {
+            let (pa, pb) = (points(&a), points(&b));
+            prop_assert_eq!(a.len(), u128::try_from(pa.len()).unwrap());
+            prop_assert_eq!(a.contains(p), pa.contains(&p));
+            prop_assert_eq!(a.contains_interval(&b), pb.is_subset(&pa));
+            prop_assert_eq!(a.overlaps(&b), !pa.is_disjoint(&pb));
+            let common: BTreeSet<_> = pa.intersection(&pb).copied().collect();
+            prop_assert_eq!(a.intersection(&b).map(|iv| points(&iv)).unwrap_or_default(), common);
+            let all: BTreeSet<_> = pa.union(&pb).copied().collect();
+            let contiguous = all.len() == usize::try_from(all.last().unwrap() - all.first().unwrap()).unwrap() + 1;
+            prop_assert_eq!(a.union(&b).map(|iv| points(&iv)), contiguous.then_some(all));
+        }
+
+        #[test]
+        fn interval_set_matches_model(
+            ivs in prop::collection::vec(interval(), 0..12),
+            query in interval(),
+        ) {
+            let set: IntervalSet<i8> = ivs.iter().copied().collect();
+            let model: BTreeSet<i8> = ivs.iter().flat_map(points).collect();
+
+            for pair in set.intervals().windows(2) {
+                // sorted, with gaps between the intervals
+                prop_assert!(pair[0].end().succ().unwrap() < pair[1].start());
+            }
+            prop_assert_eq!(set.len(), u128::try_from(model.len()).unwrap());
+            for p in -25..35 {
+                prop_assert_eq!(set.contains(p), model.contains(&p));
+            }
+            let query_points = points(&query);
+            prop_assert_eq!(set.covers(&query), query_points.is_subset(&model));
+            prop_assert_eq!(
+                set.coverage(&query),
+                u128::try_from(query_points.intersection(&model).count()).unwrap()
+            );
+        }
+    }
+}
@@ -1,11 +1,27 @@
 use std::{
+    fmt::Display,
     fs::File,
+    io::Read,
     path::{Path, PathBuf},
+    str::FromStr,
 };
 
 use anyhow::{bail, Context, Result};
+use once_cell::sync::OnceCell;
 
+use crate::diagnostic::Diagnostic;
+
+pub mod answers;
+pub mod bench;
+pub mod days;
+pub mod diagnostic;
 pub mod extra_itertools;
+pub mod grid;
+pub mod interval;
+pub mod lines;
+pub mod parsers;
+pub mod remote;
+pub mod scan;
 
 #[macro_export]
 macro_rules! open {
@@ -14,57 +30,439 @@ macro_rules! open {
     }};
 }
 
+#[macro_export]
+macro_rules! read {
+    ($file_name:expr) => {{
+        $crate::read_file(file!(), $file_name)
+    }};
+}
+
 #[inline]
 pub fn open_file(src_file: &str, file_name: impl AsRef<Path>) -> Result<File> {
-    File::open(resolve_file(src_file, file_name)?).context("Can't open file")
+    let day = Path::new(src_file)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("Can't determine AoC day")?;
+    open_data_file(day, file_name)
 }
 
-fn resolve_file(src_file: &str, file_name: impl AsRef<Path>) -> Result<PathBuf> {
-    let mut task_data_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    if !task_data_dir.pop() {
-        bail!(
-            "Something wrong with build path {}",
-            task_data_dir.display()
-        );
+#[inline]
+pub fn open_day_file(day: u8, file_name: impl AsRef<Path>) -> Result<File> {
+    open_data_file(&format!("{day:02}"), file_name)
+}
+
+#[inline]
+pub fn read_file(src_file: &str, file_name: impl AsRef<Path>) -> Result<String> {
+    read_input(open_file(src_file, file_name)?)
+}
+
+#[inline]
+pub fn read_day_file(day: u8, file_name: impl AsRef<Path>) -> Result<String> {
+    read_input(open_day_file(day, file_name)?)
+}
+
+/// Reads the whole input from any reader: file, stdin, in-memory buffer...
+pub fn read_input(mut r: impl Read) -> Result<String> {
+    let mut input = String::new();
+    r.read_to_string(&mut input).context("Can't read input")?;
+    Ok(input)
+}
+
+/// Where the puzzle input comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    /// Arbitrary file
+    Path(PathBuf),
+    /// File in the data dir of the day, ie `input.txt`
+    DataFile(String),
+    /// Input that was already read into memory
+    Text(String),
+}
+
+impl InputSource {
+    pub fn read(&self, day: u8) -> Result<String> {
+        match self {
+            Self::Stdin => read_input(std::io::stdin().lock()),
+            Self::Path(path) => read_input(
+                File::open(path).with_context(|| format!("Can't open file {}", path.display()))?,
+            ),
+            Self::DataFile(name) => read_day_file(day, name),
+            Self::Text(text) => Ok(text.clone()),
+        }
     }
-    task_data_dir.push("data");
-    task_data_dir.push(
-        Path::new(src_file)
-            .file_stem()
-            .context("Can't determine AoC day")?,
-    );
-    if !task_data_dir.is_dir() {
-        bail!("Task data dir not found at {}", task_data_dir.display());
+
+    /// Name of the file in the data dir, answers are known only for those
+    pub fn data_file(&self) -> Option<&str> {
+        match self {
+            Self::DataFile(name) => Some(name),
+            Self::Stdin | Self::Path(_) | Self::Text(_) => None,
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = anyhow::Error;
+
+    /// `-` is stdin, anything with a path separator is a path, the rest are data dir file names
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(if s == "-" {
+            Self::Stdin
+        } else if s.contains(std::path::is_separator) {
+            Self::Path(s.into())
+        } else if s.is_empty() {
+            bail!("Empty input name");
+        } else {
+            Self::DataFile(s.to_owned())
+        })
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stdin => f.write_str("stdin"),
+            Self::Path(path) => path.display().fmt(f),
+            Self::DataFile(name) => name.fmt(f),
+            Self::Text(_) => f.write_str("in-memory input"),
+        }
     }
+}
+
+fn open_data_file(day: &str, file_name: impl AsRef<Path>) -> Result<File> {
+    let path = resolve_file(day, file_name)?;
+    File::open(&path).with_context(|| format!("Can't open file {}", path.display()))
+}
+
+fn resolve_file(day: &str, file_name: impl AsRef<Path>) -> Result<PathBuf> {
+    let mut task_data_dir = task_data_dir(day)?;
     task_data_dir.push(file_name);
     Ok(task_data_dir)
 }
 
+/// Names of all the example inputs of the day (`test*.txt` and `example*.txt`), sorted
+pub fn example_files(day: u8) -> Result<Vec<String>> {
+    let dir = task_data_dir(&format!("{day:02}"))?;
+    let mut res = Vec::new();
+    for entry in dir
+        .read_dir()
+        .with_context(|| format!("Can't read {}", dir.display()))?
+    {
+        let entry = entry.context("Can't read dir entry")?;
+        let Some(name) = entry.file_name().to_str().map(str::to_owned) else {
+            continue;
+        };
+        if (name.starts_with("test") || name.starts_with("example"))
+            && name.ends_with(".txt")
+            && entry.path().is_file()
+        {
+            res.push(name);
+        }
+    }
+    res.sort();
+    Ok(res)
+}
+
+static DATA_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+pub const DATA_DIR_ENV: &str = "AOC_DATA_DIR";
+
+/// Overrides the data dir for the rest of the run, takes precedence over `AOC_DATA_DIR`
+pub fn set_data_dir(dir: impl Into<PathBuf>) -> Result<()> {
+    DATA_DIR
+        .set(dir.into())
+        .map_err(|dir| anyhow::anyhow!("Data dir is already set, can't use {}", dir.display()))
+}
+
+/// Data dirs to look in: only the override if there is one (`set_data_dir`, then
+/// `AOC_DATA_DIR`), otherwise `data` next to the crate dir (only works inside the checkout)
+fn data_dir_candidates() -> Vec<PathBuf> {
+    let overridden = DATA_DIR.get().cloned().or_else(|| {
+        std::env::var_os(DATA_DIR_ENV)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    });
+    let mut build_data_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let build_data_dir = build_data_dir.pop().then(|| build_data_dir.join("data"));
+    choose_data_dirs(overridden, build_data_dir)
+}
+
+/// An explicit dir must not silently fall back to the checkout
+fn choose_data_dirs(overridden: Option<PathBuf>, build_data_dir: Option<PathBuf>) -> Vec<PathBuf> {
+    overridden.or(build_data_dir).into_iter().collect()
+}
+
+/// First of the `candidates` that contains the dir for the day
+fn find_task_data_dir(day: &str, candidates: &[PathBuf]) -> Result<PathBuf> {
+    let tried: Vec<_> = candidates.iter().map(|dir| dir.join(day)).collect();
+    first_dir(tried).context("Task data dir not found")
+}
+
+/// First existing dir, error lists all the tried paths
+fn first_dir(tried: Vec<PathBuf>) -> Result<PathBuf> {
+    if let Some(dir) = tried.iter().find(|dir| dir.is_dir()) {
+        return Ok(dir.clone());
+    }
+    bail!(
+        "Tried:{}",
+        tried
+            .iter()
+            .map(|dir| format!("\n  {}", dir.display()))
+            .collect::<String>()
+    );
+}
+
+fn task_data_dir(day: &str) -> Result<PathBuf> {
+    find_task_data_dir(day, &data_dir_candidates())
+}
+
+/// Dir with the data of the day (`data/NN`)
+pub fn day_data_dir(day: u8) -> Result<PathBuf> {
+    task_data_dir(&format!("{day:02}"))
+}
+
+/// Root of the data dir (`data`), new days are created here
+pub fn data_dir() -> Result<PathBuf> {
+    first_dir(data_dir_candidates()).context("Data dir not found")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Self; 2] = [Self::One, Self::Two];
+}
+
+impl TryFrom<u8> for Part {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        Ok(match value {
+            1 => Self::One,
+            2 => Self::Two,
+            _ => bail!("Incorrect part: {value}"),
+        })
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::One => f.write_str("1"),
+            Self::Two => f.write_str("2"),
+        }
+    }
+}
+
+/// Solution for one day: the input is parsed once and then shared by both parts
+pub trait Solution {
+    /// Parsed puzzle input, may borrow from the raw input
+    type Input<'input>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>>;
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Answer2>;
+
+    /// Other implementations of the parts, benchmarked and checked against the main ones
+    fn alternatives() -> Vec<Alternative<Self>> {
+        Vec::new()
+    }
+
+    #[inline]
+    fn solve_part1(input: &str) -> Result<Self::Answer1> {
+        Self::part1(&Self::parse(input)?)
+    }
+
+    #[inline]
+    fn solve_part2(input: &str) -> Result<Self::Answer2> {
+        Self::part2(&Self::parse(input)?)
+    }
+}
+
+pub enum AltRun<S: Solution + ?Sized> {
+    /// Works on the parsed input, just like `part1`/`part2`
+    Parsed(for<'input> fn(&S::Input<'input>) -> Result<String>),
+    /// Does its own parsing
+    Raw(fn(&str) -> Result<String>),
+}
+
+pub struct Alternative<S: Solution + ?Sized> {
+    pub name: &'static str,
+    pub part: Part,
+    pub run: AltRun<S>,
+}
+
+impl<S: Solution + ?Sized> Alternative<S> {
+    /// `part1/<name>`
+    pub fn full_name(&self) -> String {
+        format!("part{}/{}", self.part, self.name)
+    }
+}
+
+/// Parses the input once and runs the requested parts, answers are converted to strings.
+/// Outer error means the input couldn't be parsed.
+pub fn solve<S: Solution>(input: &str, parts: &[Part]) -> Result<Vec<(Part, Result<String>)>> {
+    let input = S::parse(input).context("Failed to parse input")?;
+    Ok(parts
+        .iter()
+        .map(|&part| {
+            let answer = match part {
+                Part::One => S::part1(&input).map(|a| a.to_string()),
+                Part::Two => S::part2(&input).map(|a| a.to_string()),
+            };
+            (part, answer)
+        })
+        .collect())
+}
+
+/// Runs all the alternatives, answers are returned with the full names of the alternatives
+pub fn solve_alternatives<S: Solution>(input: &str) -> Result<Vec<(String, Part, Result<String>)>> {
+    let parsed = S::parse(input).context("Failed to parse input")?;
+    Ok(S::alternatives()
+        .into_iter()
+        .map(|alt| {
+            let answer = match alt.run {
+                AltRun::Parsed(f) => f(&parsed),
+                AltRun::Raw(f) => f(input),
+            };
+            (alt.full_name(), alt.part, answer)
+        })
+        .collect())
+}
+
 pub type Pres<'input, C> = nom::IResult<&'input str, C, nom::error::VerboseError<&'input str>>;
 
+/// nom errors that can point at the place in the input
+pub trait ToDiagnostic {
+    fn to_diagnostic(&self, input: &str) -> Diagnostic;
+}
+
+impl ToDiagnostic for nom::error::VerboseError<&str> {
+    fn to_diagnostic(&self, input: &str) -> Diagnostic {
+        Diagnostic::from_verbose(input, self)
+    }
+}
+
+impl ToDiagnostic for nom::error::Error<&str> {
+    fn to_diagnostic(&self, input: &str) -> Diagnostic {
+        Diagnostic::from_error(input, self)
+    }
+}
+
 pub trait NomFinish<I, O> {
+    /// Errors are [`Diagnostic`]s, unparsed input (other than whitespace) is an error too
     fn finish(self, input: impl AsRef<str>) -> anyhow::Result<O>;
+    /// Same as `finish`, but whatever is left unparsed is ignored
+    fn finish_partial(self, input: impl AsRef<str>) -> anyhow::Result<O>;
+    /// Strict `finish`: the parser must consume everything, including trailing newlines
+    fn finish_all(self, input: impl AsRef<str>) -> anyhow::Result<O>;
 }
 
-impl<I, O> NomFinish<I, O> for nom::IResult<I, O, nom::error::VerboseError<&str>> {
+impl<'i, O, E: ToDiagnostic> NomFinish<&'i str, O> for nom::IResult<&'i str, O, E> {
     #[inline]
     fn finish(self, input: impl AsRef<str>) -> anyhow::Result<O> {
+        let input = input.as_ref();
         match nom::Finish::finish(self) {
-            Ok((_rest, res)) => Ok(res),
-            Err(e) => anyhow::bail!(
-                "Parse error:\n{}",
-                nom::error::convert_error(input.as_ref(), e)
-            ),
+            Ok((rest, _)) if !rest.trim().is_empty() => {
+                Err(Diagnostic::unparsed(input, rest.trim_start()).into())
+            }
+            Ok((_, res)) => Ok(res),
+            Err(e) => Err(e.to_diagnostic(input).into()),
         }
     }
-}
 
-impl<I, O> NomFinish<I, O> for nom::IResult<I, O, nom::error::Error<&str>> {
     #[inline]
-    fn finish(self, _input: impl AsRef<str>) -> anyhow::Result<O> {
+    fn finish_all(self, input: impl AsRef<str>) -> anyhow::Result<O> {
+        let input = input.as_ref();
         match nom::Finish::finish(self) {
-            Ok((_rest, res)) => Ok(res),
-            Err(e) => anyhow::bail!("Parse error: {e}"),
+            Ok((rest, _)) if !rest.is_empty() => Err(Diagnostic::unparsed(input, rest).into()),
+            Ok((_, res)) => Ok(res),
+            Err(e) => Err(e.to_diagnostic(input).into()),
         }
     }
+
+    #[inline]
+    fn finish_partial(self, input: impl AsRef<str>) -> anyhow::Result<O> {
+        match nom::Finish::finish(self) {
+            Ok((_, res)) => Ok(res),
+            Err(e) => Err(e.to_diagnostic(input.as_ref()).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_task_data_dir() {
+        let root = std::env::temp_dir().join(format!("aoc-data-{}", std::process::id()));
+        let (first, second) = (root.join("first"), root.join("second"));
+        std::fs::create_dir_all(second.join("05")).unwrap();
+        let candidates = [first.clone(), second.clone()];
+
+        let res = find_task_data_dir("05", &candidates);
+        let err = format!("{:#}", find_task_data_dir("06", &candidates).unwrap_err());
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(res.unwrap(), second.join("05"));
+        assert!(err.contains(&first.join("06").display().to_string()));
+        assert!(err.contains(&second.join("06").display().to_string()));
+    }
+
+    #[test]
+    fn test_choose_data_dirs() {
+        let (dir, build) = (PathBuf::from("/tmp/empty"), PathBuf::from("/checkout/data"));
+        assert_eq!(
+            choose_data_dirs(Some(dir.clone()), Some(build.clone())),
+            [dir.as_path()]
+        );
+        assert_eq!(choose_data_dirs(None, Some(build.clone())), [build]);
+        assert!(choose_data_dirs(None, None).is_empty());
+
+        let err = format!(
+            "{:#}",
+            find_task_data_dir("01", std::slice::from_ref(&dir)).unwrap_err()
+        );
+        assert!(err.contains(&dir.join("01").display().to_string()), "{err}");
+    }
+
+    #[test]
+    fn test_input_source() {
+        assert_eq!("-".parse::<InputSource>().unwrap(), InputSource::Stdin);
+        assert_eq!(
+            "test.txt".parse::<InputSource>().unwrap(),
+            InputSource::DataFile("test.txt".to_owned())
+        );
+        assert_eq!(
+            "./test.txt".parse::<InputSource>().unwrap(),
+            InputSource::Path("./test.txt".into())
+        );
+        assert!("".parse::<InputSource>().is_err());
+        assert_eq!(read_input("in memory".as_bytes()).unwrap(), "in memory");
+    }
+
+    #[test]
+    fn test_nom_finish() {
+        let digits = |input| -> Pres<'_, &str> { nom::character::complete::digit1(input) };
+        assert_eq!(digits("12\n").finish("12\n").unwrap(), "12");
+        assert!(digits("12\n").finish_all("12\n").is_err());
+        assert_eq!(digits("12").finish_all("12").unwrap(), "12");
+        assert!(digits("12 3").finish("12 3").is_err());
+        assert_eq!(digits("12 3").finish_partial("12 3").unwrap(), "12");
+
+        let err = digits("1\n2\n").finish_all("1\n2\n").unwrap_err();
+        let err = err.downcast::<Diagnostic>().unwrap();
+        assert_eq!((err.line, err.column), (1, 2));
+        assert!(err.message.contains("3 bytes (2 lines)"), "{}", err.message);
+
+        let err = digits("12x").finish_all("12x").unwrap_err();
+        let err = err.downcast::<Diagnostic>().unwrap();
+        assert!(err.message.contains(" 1 byte (1 line)"), "{}", err.message);
+    }
 }
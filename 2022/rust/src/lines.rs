@@ -0,0 +1,104 @@
+use anyhow::{bail, Context, Result};
+
+/// 1-based line number and the trimmed line
+pub type Record<'input> = (usize, &'input str);
+
+/// Adds the line number and the line itself to the error
+pub fn at_line<T>((number, line): Record<'_>, res: Result<T>) -> Result<T> {
+    res.with_context(|| format!("Line {number}: '{line}'"))
+}
+
+/// Line-oriented view of the input, empty lines are skipped unless they separate groups
+#[derive(Debug, Clone, Copy)]
+pub struct Lines<'input>(&'input str);
+
+impl<'input> Lines<'input> {
+    pub fn new(input: &'input str) -> Self {
+        Self(input)
+    }
+
+    fn all(self) -> impl Iterator<Item = Record<'input>> {
+        self.0
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .map(|(i, l)| (i + 1, l))
+    }
+
+    /// Non-empty lines
+    pub fn records(self) -> impl Iterator<Item = Record<'input>> {
+        self.all().filter(|(_, line)| !line.is_empty())
+    }
+
+    /// Runs `f` on every non-empty line, errors are reported with the line
+    pub fn parse<T, F>(self, mut f: F) -> impl Iterator<Item = Result<T>> + use<'input, T, F>
+    where
+        F: FnMut(&'input str) -> Result<T>,
+    {
+        self.records().map(move |rec| at_line(rec, f(rec.1)))
+    }
+
+    /// Lines separated by one or more empty lines, there are no empty groups
+    pub fn groups(self) -> impl Iterator<Item = Vec<Record<'input>>> {
+        let mut lines = self.all().peekable();
+        std::iter::from_fn(move || {
+            while lines.next_if(|(_, line)| line.is_empty()).is_some() {}
+            let group: Vec<_> =
+                std::iter::from_fn(|| lines.next_if(|(_, line)| !line.is_empty())).collect();
+            (!group.is_empty()).then_some(group)
+        })
+    }
+
+    /// Consecutive non-empty lines by `N`, incomplete last chunk is an error
+    pub fn chunks<const N: usize>(self) -> impl Iterator<Item = Result<[Record<'input>; N]>> {
+        let mut records = self.records();
+        std::iter::from_fn(move || {
+            let chunk: Vec<_> = records.by_ref().take(N).collect();
+            let &(first, _) = chunk.first()?;
+            Some(chunk.try_into().or_else(|chunk: Vec<_>| {
+                bail!(
+                    "Incomplete group of {} line(s) starting at line {first}, expected {N}",
+                    chunk.len()
+                )
+            }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records() {
+        let lines = Lines::new("\n a \n\nb\n");
+        assert_eq!(lines.records().collect::<Vec<_>>(), [(2, "a"), (4, "b")]);
+
+        let res: Result<Vec<u32>> = Lines::new("1\n\n2x\n").parse(|l| Ok(l.parse()?)).collect();
+        let err = format!("{:#}", res.unwrap_err());
+        assert!(err.starts_with("Line 3: '2x': "), "{err}");
+    }
+
+    #[test]
+    fn test_groups() {
+        let groups: Vec<_> = Lines::new("\n\na\nb\n\n\n\nc\n\n").groups().collect();
+        assert_eq!(groups, [vec![(3, "a"), (4, "b")], vec![(8, "c")]]);
+        assert_eq!(Lines::new("\n \n").groups().count(), 0);
+        assert_eq!(Lines::new("a").groups().count(), 1);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let chunks: Vec<_> = Lines::new("a\nb\n\nc\nd\n")
+            .chunks::<2>()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(chunks, [[(1, "a"), (2, "b")], [(4, "c"), (5, "d")]]);
+
+        let mut chunks = Lines::new("a\nb\nc").chunks::<2>();
+        assert!(chunks.next().unwrap().is_ok());
+        let err = chunks.next().unwrap().unwrap_err().to_string();
+        assert!(err.contains("1 line(s) starting at line 3"), "{err}");
+        assert!(chunks.next().is_none());
+    }
+}
@@ -0,0 +1,93 @@
+//! Reusable nom parsers, producing [`Pres`] with context labels for the [`crate::diagnostic`]s
+
+use crate::Pres;
+use nom::{
+    character::complete::{digit1, line_ending, one_of, space1},
+    combinator::{map_res, opt, recognize},
+    error::context,
+    multi::separated_list1,
+    sequence::{pair, terminated},
+};
+use std::str::FromStr;
+
+/// Digits only, no sign
+pub fn uint<T: FromStr>(input: &str) -> Pres<'_, T> {
+    context("unsigned integer", map_res(digit1, str::parse))(input)
+}
+
+/// Digits with an optional leading `-` or `+`
+pub fn int<T: FromStr>(input: &str) -> Pres<'_, T> {
+    context(
+        "integer",
+        map_res(recognize(pair(opt(one_of("-+")), digit1)), |s: &str| {
+            // `FromStr` of the unsigned types rejects `-`, but they should take `+1` as well
+            s.strip_prefix('+').unwrap_or(s).parse()
+        }),
+    )(input)
+}
+
+/// One or more items on a single line, separated by spaces or tabs
+pub fn spaced<'input, O>(
+    item: impl FnMut(&'input str) -> Pres<'input, O>,
+) -> impl FnMut(&'input str) -> Pres<'input, Vec<O>> {
+    context("list", separated_list1(space1, item))
+}
+
+/// One or more lines, each one parsed by `line`, with an optional final newline
+pub fn lines_of<'input, O>(
+    line: impl FnMut(&'input str) -> Pres<'input, O>,
+) -> impl FnMut(&'input str) -> Pres<'input, Vec<O>> {
+    context(
+        "lines",
+        terminated(separated_list1(line_ending, line), opt(line_ending)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NomFinish;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(uint::<u8>("42 x"), Ok((" x", 42)));
+        assert!(uint::<u8>("").is_err());
+        assert!(uint::<u8>("-1").is_err());
+        assert!(uint::<u8>("256").is_err());
+        assert_eq!(int::<i32>("-42"), Ok(("", -42)));
+        assert_eq!(int::<i32>("+42"), Ok(("", 42)));
+        assert_eq!(int::<u64>("+7"), Ok(("", 7)));
+        assert!(int::<u64>("-7").is_err());
+        assert!(int::<i8>("-").is_err());
+        assert!(int::<i8>("--1").is_err());
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(spaced(int::<i8>)("1 -2\t3\n4"), Ok(("\n4", vec![1, -2, 3])));
+        let input = "1 2\n3\n";
+        assert_eq!(
+            lines_of(spaced(uint::<u8>))(input)
+                .finish_all(input)
+                .unwrap(),
+            [vec![1, 2], vec![3]]
+        );
+        assert_eq!(lines_of(uint::<u8>)("1\r\n2"), Ok(("", vec![1, 2])));
+    }
+
+    #[test]
+    fn test_context() {
+        let input = "1\nx\n";
+        let err = format!(
+            "{:#}",
+            pair(uint::<u8>, pair(line_ending, uint::<u8>))(input)
+                .finish(input)
+                .unwrap_err()
+        );
+        assert!(err.contains("2 | x\n  | ^"), "{err}");
+        assert!(
+            err.contains("while parsing unsigned integer at 2:1"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,430 @@
+use anyhow::{bail, Context, Result};
+
+use crate::Part;
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2022;
+pub const SESSION_ENV: &str = "AOC_SESSION";
+const USER_AGENT: &str = "github.com/Andrew-Morozko/advent-of-code/tree/main/2022/rust";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Does the actual HTTP requests, so that the client can be tested against a local server
+pub trait HttpBackend {
+    /// GET with the session cookie, non-2xx statuses are returned as responses, not errors
+    fn get(&self, url: &str, session: &str) -> Result<Response>;
+    /// POST of an url-encoded form, same as `get` otherwise
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response>;
+}
+
+pub struct UreqBackend {
+    agent: ureq::Agent,
+}
+
+impl Default for UreqBackend {
+    fn default() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+}
+
+impl UreqBackend {
+    fn response(res: Result<ureq::Response, ureq::Error>) -> Result<Response> {
+        let res = match res {
+            Ok(res) | Err(ureq::Error::Status(_, res)) => res,
+            Err(e) => return Err(e).context("Request failed"),
+        };
+        Ok(Response {
+            status: res.status(),
+            body: res.into_string().context("Can't read response")?,
+        })
+    }
+}
+
+impl HttpBackend for UreqBackend {
+    fn get(&self, url: &str, session: &str) -> Result<Response> {
+        Self::response(
+            self.agent
+                .get(url)
+                .set("Cookie", &format!("session={session}"))
+                .call(),
+        )
+    }
+
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response> {
+        Self::response(
+            self.agent
+                .post(url)
+                .set("Cookie", &format!("session={session}"))
+                .send_form(form),
+        )
+    }
+}
+
+/// Keeps at least `min_interval` between the requests, even across runs:
+/// the time of the last request is stored in a file
+pub struct RateLimiter {
+    state_file: PathBuf,
+    min_interval: Duration,
+}
+
+impl RateLimiter {
+    pub const STATE_FILE: &'static str = ".last_request";
+
+    pub fn new(state_file: impl Into<PathBuf>, min_interval: Duration) -> Self {
+        Self {
+            state_file: state_file.into(),
+            min_interval,
+        }
+    }
+
+    fn last_request(&self) -> Option<Duration> {
+        let millis = fs::read_to_string(&self.state_file)
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(Duration::from_millis(millis))
+    }
+
+    /// Sleeps until the next request is allowed and records it as made
+    pub fn wait(&self) -> Result<()> {
+        let now = || SystemTime::now().duration_since(UNIX_EPOCH);
+        if let Some(last) = self.last_request() {
+            let next = last + self.min_interval;
+            let now = now()?;
+            if next > now {
+                thread::sleep(next - now);
+            }
+        }
+        // Rounded up, so that the next wait is never shorter than the interval
+        let millis = now()?.as_micros().div_ceil(1000);
+        fs::write(&self.state_file, millis.to_string())
+            .with_context(|| format!("Can't write {}", self.state_file.display()))
+    }
+}
+
+pub struct Client<B> {
+    backend: B,
+    base_url: String,
+    session: String,
+    /// Root of the data dir, inputs are cached at `NN/input.txt`
+    data_dir: PathBuf,
+    limiter: RateLimiter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    /// Extracts the verdict from the html page the server responds with
+    fn from_page(page: &str) -> Result<Self> {
+        Ok(if page.contains("That's the right answer") {
+            Self::Correct
+        } else if page.contains("your answer is too high") {
+            Self::TooHigh
+        } else if page.contains("your answer is too low") {
+            Self::TooLow
+        } else if page.contains("That's not the right answer") {
+            Self::Wrong
+        } else if page.contains("You gave an answer too recently") {
+            // Not a verdict, so it isn't recorded
+            let wait = page
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map_or("some time", |(wait, _)| wait);
+            bail!("Answer was submitted too recently, {wait} left to wait");
+        } else if page.contains("Did you already complete it") {
+            bail!("This part is already solved or not unlocked yet");
+        } else {
+            bail!("Can't find the verdict in the response");
+        })
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Correct => "correct",
+            Self::Wrong => "wrong",
+            Self::TooHigh => "too high",
+            Self::TooLow => "too low",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "correct" => Self::Correct,
+            "wrong" => Self::Wrong,
+            "too high" => Self::TooHigh,
+            "too low" => Self::TooLow,
+            _ => bail!("Unknown verdict '{s}'"),
+        })
+    }
+}
+
+/// Every answer submitted for a day, stored as tab separated `part answer verdict` lines.
+/// Used to avoid wasting guesses (and the timeouts that come with the wrong ones).
+#[derive(Debug, Default)]
+pub struct History(Vec<(Part, String, Verdict)>);
+
+impl History {
+    pub const FILE_NAME: &'static str = "guesses.tsv";
+
+    /// Missing file means that nothing was submitted yet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data =
+            fs::read_to_string(path).with_context(|| format!("Can't read {}", path.display()))?;
+        Self::parse(&data).with_context(|| format!("Can't parse {}", path.display()))
+    }
+
+    pub fn parse(data: &str) -> Result<Self> {
+        data.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let mut fields = line.split('\t');
+                let mut next = || fields.next().with_context(|| format!("Bad line {line}"));
+                let part = Part::try_from(next()?.parse::<u8>()?)?;
+                let answer = next()?.to_owned();
+                let verdict = next()?.parse()?;
+                Ok((part, answer, verdict))
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let data: String = self
+            .0
+            .iter()
+            .map(|(part, answer, verdict)| format!("{part}\t{answer}\t{verdict}\n"))
+            .collect();
+        let path = path.as_ref();
+        fs::write(path, data).with_context(|| format!("Can't write {}", path.display()))
+    }
+
+    pub fn guesses(&self, part: Part) -> impl Iterator<Item = (&str, Verdict)> {
+        self.0
+            .iter()
+            .filter(move |(p, _, _)| *p == part)
+            .map(|(_, answer, verdict)| (answer.as_str(), *verdict))
+    }
+
+    /// Exclusive bounds of numeric answers from the "too low"/"too high" hints
+    pub fn bounds(&self, part: Part) -> (Option<i64>, Option<i64>) {
+        let (mut low, mut high) = (None::<i64>, None::<i64>);
+        for (answer, verdict) in self.guesses(part) {
+            let Ok(n) = answer.parse() else { continue };
+            match verdict {
+                Verdict::TooLow => low = Some(low.map_or(n, |low| low.max(n))),
+                Verdict::TooHigh => high = Some(high.map_or(n, |high| high.min(n))),
+                Verdict::Correct | Verdict::Wrong => {}
+            }
+        }
+        (low, high)
+    }
+
+    /// Fails if the answer is known to be wrong without asking the server
+    pub fn check(&self, part: Part, answer: &str) -> Result<()> {
+        for (guess, verdict) in self.guesses(part) {
+            match verdict {
+                Verdict::Correct if guess == answer => bail!("{answer} is already accepted"),
+                Verdict::Correct => bail!("Part is already solved, the answer is {guess}"),
+                _ if guess == answer => bail!("{answer} was already submitted: {verdict}"),
+                _ => {}
+            }
+        }
+        let (low, high) = self.bounds(part);
+        if low.is_some() || high.is_some() {
+            let n: i64 = answer
+                .parse()
+                .with_context(|| format!("{answer} isn't a number, but earlier guesses were"))?;
+            if let Some(low) = low.filter(|&low| n <= low) {
+                bail!("{answer} is too low, {low} was already too low");
+            }
+            if let Some(high) = high.filter(|&high| n >= high) {
+                bail!("{answer} is too high, {high} was already too high");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn record(&mut self, part: Part, answer: &str, verdict: Verdict) {
+        self.0.push((part, answer.to_owned(), verdict));
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// Already downloaded before, no requests were made
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+impl<B: HttpBackend> Client<B> {
+    pub fn new(
+        backend: B,
+        base_url: impl Into<String>,
+        session: impl Into<String>,
+        data_dir: impl Into<PathBuf>,
+        min_interval: Duration,
+    ) -> Self {
+        let data_dir = data_dir.into();
+        Self {
+            backend,
+            base_url: base_url.into(),
+            session: session.into(),
+            limiter: RateLimiter::new(data_dir.join(RateLimiter::STATE_FILE), min_interval),
+            data_dir,
+        }
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    fn url(&self, day: u8, path: &str) -> String {
+        format!(
+            "{}/{YEAR}/day/{day}{path}",
+            self.base_url.trim_end_matches('/')
+        )
+    }
+
+    /// Downloads the input of the day into `NN/input.txt`, unless it's already there
+    pub fn fetch_input(&self, day: u8) -> Result<Fetched> {
+        let dir = self.data_dir.join(format!("{day:02}"));
+        let path = dir.join("input.txt");
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+
+        self.limiter.wait()?;
+        let res = self.backend.get(&self.url(day, "/input"), &self.session)?;
+        match res.status {
+            200..=299 => {}
+            400 | 500 => bail!("Server rejected the request, is the session token valid?"),
+            404 => bail!("Input for day {day} isn't available yet"),
+            status => bail!("Unexpected status {status}: {}", res.body.trim()),
+        }
+        if res.body.is_empty() {
+            bail!("Server returned an empty input");
+        }
+
+        fs::create_dir_all(&dir).with_context(|| format!("Can't create {}", dir.display()))?;
+        fs::write(&path, res.body).with_context(|| format!("Can't write {}", path.display()))?;
+        Ok(Fetched::Downloaded(path))
+    }
+
+    /// Path to the guess history of the day
+    pub fn history_file(&self, day: u8) -> PathBuf {
+        self.data_dir
+            .join(format!("{day:02}"))
+            .join(History::FILE_NAME)
+    }
+
+    /// Submits the answer unless the history already tells that it's wrong.
+    /// Every verdict is recorded in the history.
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Verdict> {
+        let answer = answer.trim();
+        if answer.is_empty() || answer.contains(['\t', '\n']) {
+            bail!("Can't submit '{answer}'");
+        }
+        let path = self.history_file(day);
+        let mut history = History::load(&path)?;
+        history.check(part, answer)?;
+
+        self.limiter.wait()?;
+        let level = part.to_string();
+        let res = self.backend.post(
+            &self.url(day, "/answer"),
+            &self.session,
+            &[("level", &level), ("answer", answer)],
+        )?;
+        match res.status {
+            200..=299 => {}
+            400 | 500 => bail!("Server rejected the request, is the session token valid?"),
+            status => bail!("Unexpected status {status}: {}", res.body.trim()),
+        }
+        let verdict = Verdict::from_page(&res.body)?;
+
+        history.record(part, answer, verdict);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Can't create {}", dir.display()))?;
+        }
+        history.save(&path)?;
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history() {
+        let history = History::parse(
+            "1\t100\ttoo low\n1\t200\ttoo high\n1\t150\twrong\n1\t180\ttoo high\n2\tABC\tcorrect\n",
+        )
+        .unwrap();
+
+        assert_eq!(history.bounds(Part::One), (Some(100), Some(180)));
+        assert!(history.check(Part::One, "120").is_ok());
+        for answer in ["100", "50", "150", "180", "190", "abc"] {
+            assert!(history.check(Part::One, answer).is_err(), "{answer}");
+        }
+        assert!(history.check(Part::Two, "ABC").is_err());
+        assert!(history.check(Part::Two, "ABD").is_err());
+
+        assert!(History::parse("1\t100\tmaybe").is_err());
+        assert!(History::parse("3\t100\twrong").is_err());
+    }
+
+    #[test]
+    fn test_verdict() {
+        let page = |text| format!("<main><article><p>{text}</p></article></main>");
+        let verdict = |text| Verdict::from_page(&page(text));
+        assert_eq!(
+            verdict("That's the right answer! You are one gold star closer.").unwrap(),
+            Verdict::Correct
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too low.").unwrap(),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            verdict("That's not the right answer.  If you're stuck...").unwrap(),
+            Verdict::Wrong
+        );
+        let too_soon = verdict("You gave an answer too recently. You have 38s left to wait.");
+        assert!(too_soon.unwrap_err().to_string().contains("38s left"));
+    }
+}
@@ -0,0 +1,150 @@
+use anyhow::{bail, Context, Result};
+use std::{any::type_name, str::FromStr};
+
+/// Extracts typed fields from a line by a template, `{}` is a field, the rest must match as is:
+///
+/// ```
+/// let (a, b) = aoc::scan!("{}-{}", "2-4" => u64, u8)?;
+/// assert_eq!((a, b), (2, 4));
+/// # anyhow::Ok(())
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($template:literal, $line:expr => $($t:ty),+ $(,)?) => {
+        $crate::scan::Fields::new($template, $line).and_then(|mut fields| {
+            let res = ($(fields.field::<$t>()?,)+);
+            fields.finish()?;
+            ::anyhow::Ok(res)
+        })
+    };
+}
+
+/// Fields of the line, split by the literal parts of the template. Used by [`scan!`].
+pub struct Fields<'a> {
+    template: &'a str,
+    fields: Vec<&'a str>,
+    next: usize,
+}
+
+impl<'a> Fields<'a> {
+    pub fn new(template: &'a str, line: &'a str) -> Result<Self> {
+        let mut literals = template.split("{}");
+        let prefix = literals.next().unwrap_or_default();
+        let mut rest = line
+            .strip_prefix(prefix)
+            .with_context(|| format!("'{line}' doesn't start with '{prefix}'"))?;
+        let mut fields = Vec::new();
+        let mut literals = literals.peekable();
+        while let Some(literal) = literals.next() {
+            let field_no = fields.len() + 1;
+            let (field, after) = match literal {
+                // The last field takes the rest of the line
+                "" if literals.peek().is_none() => (rest, ""),
+                "" => bail!(
+                    "Fields {field_no} and {} of '{template}' aren't separated",
+                    field_no + 1
+                ),
+                literal => rest.split_once(literal).with_context(|| {
+                    format!("Expected '{literal}' after field {field_no} in '{line}'")
+                })?,
+            };
+            fields.push(field);
+            rest = after;
+        }
+        if !rest.is_empty() {
+            bail!("Unexpected '{rest}' at the end of '{line}'");
+        }
+        Ok(Self {
+            template,
+            fields,
+            next: 0,
+        })
+    }
+
+    /// Parses the next field
+    pub fn field<T>(&mut self) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Into<anyhow::Error>,
+    {
+        let field_no = self.next + 1;
+        let field = *self
+            .fields
+            .get(self.next)
+            .with_context(|| format!("'{}' has less than {field_no} fields", self.template))?;
+        self.next += 1;
+        field.parse().map_err(Into::into).with_context(|| {
+            format!(
+                "Field {field_no} ('{field}') is not a valid {}",
+                type_name::<T>()
+            )
+        })
+    }
+
+    /// Fails if some fields weren't parsed
+    pub fn finish(&self) -> Result<()> {
+        if self.next != self.fields.len() {
+            bail!(
+                "'{}' has {} fields, but {} were parsed",
+                self.template,
+                self.fields.len(),
+                self.next
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_scan() {
+        assert_eq!(
+            scan!("{}-{},{}-{}", "2-4,6-8" => u64, u64, u64, u64).unwrap(),
+            (2, 4, 6, 8)
+        );
+        assert_eq!(
+            scan!("move {} from {} to {}", "move 1 from 2 to 3" => usize, u8, i32).unwrap(),
+            (1, 2, 3)
+        );
+        assert_eq!(
+            scan!("{}: {}", "name: some text" => String, String).unwrap(),
+            ("name".to_owned(), "some text".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        fn err<T: std::fmt::Debug>(res: anyhow::Result<T>) -> String {
+            format!("{:#}", res.unwrap_err())
+        }
+        assert_eq!(
+            err(scan!("{}-{},{}-{}", "2-4,x-8" => u64, u64, u64, u64)),
+            "Field 3 ('x') is not a valid u64: invalid digit found in string"
+        );
+        assert_eq!(
+            err(scan!("{}-{},{}-{}", "2-4;6-8" => u64, u64, u64, u64)),
+            "Expected ',' after field 2 in '2-4;6-8'"
+        );
+        assert_eq!(
+            err(scan!("[{}]", "[1]x" => u8)),
+            "Unexpected 'x' at the end of '[1]x'"
+        );
+        assert_eq!(
+            err(scan!("[{}]", "1]" => u8)),
+            "'1]' doesn't start with '['"
+        );
+        assert_eq!(
+            err(scan!("{}{}", "12" => u8, u8)),
+            "Fields 1 and 2 of '{}{}' aren't separated"
+        );
+        assert_eq!(
+            err(scan!("{}-{}", "1-2" => u8)),
+            "'{}-{}' has 2 fields, but 1 were parsed"
+        );
+        assert_eq!(
+            err(scan!("{}", "1" => u8, u8)),
+            "'{}' has less than 2 fields"
+        );
+    }
+}
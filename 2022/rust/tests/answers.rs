@@ -0,0 +1,88 @@
+//! Checks every answer from `data/NN/answers.toml` against the solutions and their alternatives.
+//! Every example input (`test*.txt`, `example*.txt`) must have an entry in `answers.toml`.
+
+use aoc::{
+    answers::{Answers, Check},
+    days::{Day, DAYS},
+    example_files, read_day_file, Part,
+};
+use libtest_mimic::{Arguments, Failed, Trial};
+use std::collections::BTreeSet;
+
+fn check(
+    answers: &Answers,
+    file_name: &str,
+    part: Part,
+    name: &str,
+    answer: &anyhow::Result<String>,
+) -> Result<(), Failed> {
+    match answers.check_result(file_name, part, answer) {
+        Check::Correct => Ok(()),
+        Check::Unknown => Err(format!("{name}: no expected answer").into()),
+        Check::Wrong(expected) => Err(match answer {
+            Ok(answer) => format!("{name}: got {answer}, expected {expected}"),
+            Err(e) => format!("{name} failed: {e:?}\nexpected {expected}"),
+        }
+        .into()),
+    }
+}
+
+fn run(day: &Day, answers: &Answers, file_name: &str, part: Part) -> Result<(), Failed> {
+    let input = read_day_file(day.day, file_name)?;
+    let answer = (day.solve)(&input, &[part]).and_then(|mut answers| {
+        answers
+            .pop()
+            .map(|(_, answer)| answer)
+            .ok_or_else(|| anyhow::anyhow!("No answer"))?
+    });
+    check(answers, file_name, part, "main", &answer)?;
+    // Alternatives aren't checked if the input can't be parsed
+    if let Ok(alternatives) = (day.alternatives)(&input) {
+        for (name, alt_part, answer) in alternatives {
+            if alt_part == part {
+                check(answers, file_name, part, &name, &answer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn trials() -> anyhow::Result<Vec<Trial>> {
+    let mut trials = Vec::new();
+    for day in DAYS {
+        let answers = Answers::load(day.day)?;
+        let files: BTreeSet<String> = answers
+            .iter()
+            .map(|(file_name, _)| file_name.to_owned())
+            .chain(example_files(day.day)?)
+            .collect();
+        for file_name in files {
+            let expected = answers.get(&file_name).cloned().unwrap_or_default();
+            let mut parts = Part::BOTH
+                .into_iter()
+                .filter(|&part| expected.part(part).is_some())
+                .peekable();
+            if parts.peek().is_none() {
+                trials.push(Trial::test(
+                    format!("day{:02}::{file_name}", day.day),
+                    move || Err(format!("No answers for {file_name} in answers.toml").into()),
+                ));
+                continue;
+            }
+            for part in parts {
+                let answers = answers.clone();
+                let file_name = file_name.clone();
+                trials.push(Trial::test(
+                    format!("day{:02}::{file_name}::part{part}", day.day),
+                    move || run(day, &answers, &file_name, part),
+                ));
+            }
+        }
+    }
+    Ok(trials)
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Arguments::from_args();
+    libtest_mimic::run(&args, trials()?).exit();
+}
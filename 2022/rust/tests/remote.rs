@@ -0,0 +1,225 @@
+//! Runs the client with the real HTTP backend against a local stand-in server
+
+use aoc::{
+    remote::{Client, Fetched, History, UreqBackend, Verdict},
+    Part,
+};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone)]
+struct Request {
+    method: String,
+    path: String,
+    cookie: Option<String>,
+    body: String,
+}
+
+type Handler = dyn Fn(&Request) -> (u16, String) + Send + Sync;
+
+struct Server {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl Server {
+    fn start(handler: impl Fn(&Request) -> (u16, String) + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+        {
+            let requests = Arc::clone(&requests);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { continue };
+                    let Some(req) = read_request(&mut BufReader::new(&stream)) else {
+                        continue;
+                    };
+                    let (status, body) = handler(&req);
+                    requests.lock().unwrap().push(req);
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 {status} Whatever\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                }
+            });
+        }
+        Self { url, requests }
+    }
+
+    fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request(r: &mut impl BufRead) -> Option<Request> {
+    let mut line = String::new();
+    r.read_line(&mut line).ok()?;
+    let mut start = line.split_whitespace();
+    let method = start.next()?.to_owned();
+    let path = start.next()?.to_owned();
+
+    let (mut cookie, mut len) = (None, 0);
+    loop {
+        line.clear();
+        r.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(": ")?;
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => cookie = Some(value.to_owned()),
+            "content-length" => len = value.parse().ok()?,
+            _ => {}
+        }
+    }
+    let mut body = vec![0; len];
+    r.read_exact(&mut body).ok()?;
+    Some(Request {
+        method,
+        path,
+        cookie,
+        body: String::from_utf8(body).ok()?,
+    })
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn client(server: &Server, data_dir: &Path, interval: Duration) -> Client<UreqBackend> {
+    Client::new(
+        UreqBackend::default(),
+        &server.url,
+        "secret",
+        data_dir,
+        interval,
+    )
+}
+
+#[test]
+fn test_fetch_caches_inputs() {
+    let server = Server::start(|req| (200, format!("input for {}\n", req.path)));
+    let dir = temp_dir("fetch");
+    let client = client(&server, &dir, Duration::ZERO);
+
+    let path = dir.join("05").join("input.txt");
+    assert_eq!(
+        client.fetch_input(5).unwrap(),
+        Fetched::Downloaded(path.clone())
+    );
+    assert_eq!(
+        client.fetch_input(5).unwrap(),
+        Fetched::Cached(path.clone())
+    );
+    let input = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(input, "input for /2022/day/5/input\n");
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].cookie.as_deref(), Some("session=secret"));
+    assert_eq!(requests[0].body, "");
+}
+
+#[test]
+fn test_fetch_errors() {
+    let server = Server::start(|req| match req.path.as_str() {
+        "/2022/day/1/input" => (404, "Not yet".to_owned()),
+        _ => (400, "Bad session".to_owned()),
+    });
+    let dir = temp_dir("fetch-errors");
+    let client = client(&server, &dir, Duration::ZERO);
+
+    let not_yet = client.fetch_input(1).unwrap_err().to_string();
+    let bad_session = client.fetch_input(2).unwrap_err().to_string();
+    let created = dir.join("01").exists();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(not_yet.contains("isn't available yet"), "{not_yet}");
+    assert!(bad_session.contains("session"), "{bad_session}");
+    assert!(!created);
+}
+
+#[test]
+fn test_fetch_rate_limit() {
+    let server = Server::start(|_| (200, "input".to_owned()));
+    let dir = temp_dir("fetch-rate-limit");
+    let interval = Duration::from_millis(300);
+
+    let start = Instant::now();
+    client(&server, &dir, interval).fetch_input(1).unwrap();
+    // New client, the time of the last request is stored on disk
+    client(&server, &dir, interval).fetch_input(2).unwrap();
+    let elapsed = start.elapsed();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(elapsed >= interval, "{elapsed:?}");
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_submit_history() {
+    let server = Server::start(|req| {
+        let text = match req.body.as_str() {
+            "level=1&answer=100" => "That's not the right answer; your answer is too low.",
+            "level=1&answer=300" => "That's not the right answer; your answer is too high.",
+            "level=1&answer=200" => "That's the right answer!",
+            "level=2&answer=1" => "You gave an answer too recently. You have 42s left to wait.",
+            _ => "That's not the right answer.",
+        };
+        (200, format!("<article><p>{text}</p></article>"))
+    });
+    let dir = temp_dir("submit");
+    let client = client(&server, &dir, Duration::ZERO);
+
+    let verdicts = [
+        client.submit(1, Part::One, "100").unwrap(),
+        client.submit(1, Part::One, "300").unwrap(),
+        client.submit(1, Part::One, "150").unwrap(),
+    ];
+    // Rejected locally
+    let repeated = client.submit(1, Part::One, "150").unwrap_err().to_string();
+    let too_low = client.submit(1, Part::One, "99").unwrap_err().to_string();
+    let too_high = client.submit(1, Part::One, "301").unwrap_err().to_string();
+    let requests_before_correct = server.requests().len();
+    let correct = client.submit(1, Part::One, "200").unwrap();
+    let solved = client.submit(1, Part::One, "201").unwrap_err().to_string();
+    let too_soon = client.submit(1, Part::Two, "1").unwrap_err().to_string();
+    let history = History::load(client.history_file(1)).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        verdicts,
+        [Verdict::TooLow, Verdict::TooHigh, Verdict::Wrong]
+    );
+    assert!(repeated.contains("already submitted"), "{repeated}");
+    assert!(too_low.contains("too low"), "{too_low}");
+    assert!(too_high.contains("too high"), "{too_high}");
+    assert_eq!(requests_before_correct, 3);
+    assert_eq!(correct, Verdict::Correct);
+    assert!(solved.contains("already solved"), "{solved}");
+    assert!(too_soon.contains("42s left"), "{too_soon}");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 5);
+    assert!(requests.iter().all(|r| r.method == "POST"));
+    assert_eq!(requests[0].path, "/2022/day/1/answer");
+    assert_eq!(requests[0].cookie.as_deref(), Some("session=secret"));
+    // Too soon isn't a verdict, so it's not in the history
+    assert_eq!(history.guesses(Part::One).count(), 4);
+    assert_eq!(history.guesses(Part::Two).count(), 0);
+}